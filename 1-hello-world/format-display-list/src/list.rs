@@ -0,0 +1,590 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+// Define a structure named `List` containing a `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T> List<T> {
+    // A display adapter for changing how the list is printed, in the spirit
+    // of `Path::display`.
+    pub fn display(&self) -> ListDisplay<'_, T> {
+        ListDisplay {
+            list: self,
+            indices: true,
+            max_items: None,
+            max_width: None,
+        }
+    }
+}
+
+// Prints a `List` with configurable layout. Formatter flags given to the
+// adapter (width, fill, precision, `+`, `#`, ...) are passed through to every
+// element, and `{:#}` additionally puts each element on its own indented line.
+// Elements that span several lines themselves, such as nested lists under
+// `{:#}`, are indented as a whole.
+//
+// With `max_items` or `max_width` set, long lists are elided in the middle:
+// `[0: 1, 1: 2, … 999,996 more …, 999998: 999999, 999999: 1000000]`. Only
+// the elements that are shown get formatted, and nothing is buffered, so this
+// is safe to use on lists of any size, and the elements that are shown look
+// exactly as they would in the full list.
+pub struct ListDisplay<'a, T> {
+    list: &'a List<T>,
+    indices: bool,
+    max_items: Option<usize>,
+    max_width: Option<usize>,
+}
+
+impl<T> ListDisplay<'_, T> {
+    // Whether to label each element with its index, as in `[0: 1, 1: 2]`.
+    // On by default.
+    pub fn indices(self, indices: bool) -> Self {
+        ListDisplay { indices, ..self }
+    }
+
+    // Show at most `max_items` elements, split between the start and the end
+    // of the list.
+    pub fn max_items(self, max_items: usize) -> Self {
+        ListDisplay {
+            max_items: Some(max_items),
+            ..self
+        }
+    }
+
+    // Drop further elements from the middle until the single-line output fits
+    // in `max_width` characters. The `{:#}` layout ignores this limit.
+    pub fn max_width(self, max_width: usize) -> Self {
+        ListDisplay {
+            max_width: Some(max_width),
+            ..self
+        }
+    }
+
+    // Write the list, using `item` to format each element with the very same
+    // formatter so that its options carry over.
+    fn write_with(
+        &self,
+        f: &mut fmt::Formatter,
+        item: fn(&T, &mut fmt::Formatter) -> fmt::Result,
+        plain: PlainFn<T>,
+    ) -> fmt::Result {
+        // Extract the value using tuple indexing,
+        // and create a reference to `vec`.
+        let vec = &self.list.0;
+        let pretty = f.alternate();
+        let spec = Spec::of(f);
+        let (head, tail) = self.shown(spec, plain);
+        let hidden = vec.len() - head - tail;
+
+        // `None` stands for the marker in place of the hidden elements.
+        let marker = (hidden > 0).then_some(None);
+        let pieces = (0..head)
+            .map(Some)
+            .chain(marker)
+            .chain((vec.len() - tail..vec.len()).map(Some));
+
+        f.write_str("[")?;
+
+        // Iterate over the pieces while enumerating the iteration count in
+        // `count`.
+        for (count, piece) in pieces.enumerate() {
+            // For every piece except the first, add a comma.
+            // Use the ? operator to return on errors.
+            if pretty {
+                f.write_str("\n    ")?;
+            } else if count != 0 {
+                f.write_str(", ")?;
+            }
+            match piece {
+                None => write_marker(f, hidden)?,
+                // The element goes through `Indented`, which cannot hand on
+                // the formatter itself, so its flags are copied over.
+                Some(i) if pretty => {
+                    self.write_entry(&mut Indented(&mut *f), i, &vec[i], spec, plain)?
+                }
+                Some(i) => {
+                    if self.indices {
+                        // `write!` formats the index with default options;
+                        // only the element sees the caller's flags.
+                        write!(f, "{}: ", i)?;
+                    }
+                    item(&vec[i], f)?;
+                }
+            }
+            if pretty {
+                f.write_str(",")?;
+            }
+        }
+
+        // Close the opened bracket and return a fmt::Result value.
+        if pretty && !vec.is_empty() {
+            f.write_str("\n")?;
+        }
+        f.write_str("]")
+    }
+
+    // How many elements to show from the start and from the end of the list.
+    // Elements are taken alternately from the front and the back, giving the
+    // head the odd one out, until the item limit is reached or the next one
+    // would push the line past the width limit. Only elements that are
+    // candidates for showing are ever measured.
+    fn shown(&self, spec: Spec, plain: PlainFn<T>) -> (usize, usize) {
+        let vec = &self.list.0;
+        let limit = self.max_items.unwrap_or(usize::MAX).min(vec.len());
+        let max_width = if spec.alternate { None } else { self.max_width };
+        let (mut head, mut tail, mut items_width) = (0, 0, 0);
+
+        while head + tail < limit {
+            let from_head = head <= tail;
+            let i = if from_head { head } else { vec.len() - 1 - tail };
+
+            if let Some(max_width) = max_width {
+                let width = self.entry_width(i, &vec[i], spec, plain);
+                let shown = head + tail + 1;
+                let hidden = vec.len() - shown;
+                let marker = if hidden > 0 { marker_width(hidden) } else { 0 };
+                let pieces = shown + usize::from(hidden > 0);
+                // Brackets, elements, the marker and a `, ` between pieces.
+                if 2 + items_width + width + marker + 2 * (pieces - 1) > max_width {
+                    break;
+                }
+                items_width += width;
+            }
+
+            if from_head {
+                head += 1;
+            } else {
+                tail += 1;
+            }
+        }
+        (head, tail)
+    }
+
+    // One element with its optional index label, padded as `spec` asks.
+    fn write_entry<W: Write>(
+        &self,
+        out: &mut W,
+        index: usize,
+        v: &T,
+        spec: Spec,
+        plain: PlainFn<T>,
+    ) -> fmt::Result {
+        if self.indices {
+            write!(out, "{}: ", index)?;
+        }
+
+        // Without an explicit alignment the element's own default applies,
+        // so the width can be handed straight to it. The same goes for `0`,
+        // which pads numbers after their sign whatever the alignment.
+        let align = match spec.align {
+            Some(align) if !spec.zero => align,
+            _ => return plain(v, spec, out),
+        };
+
+        let unpadded = Spec { width: None, ..spec };
+        let mut counter = Counter(0);
+        plain(v, unpadded, &mut counter)?;
+        let padding = spec.width.unwrap_or(0).saturating_sub(counter.0);
+        let (before, after) = match align {
+            fmt::Alignment::Left => (0, padding),
+            fmt::Alignment::Right => (padding, 0),
+            fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+
+        for _ in 0..before {
+            out.write_char(spec.fill)?;
+        }
+        plain(v, unpadded, out)?;
+        for _ in 0..after {
+            out.write_char(spec.fill)?;
+        }
+        Ok(())
+    }
+
+    // Measured with every flag the element will be written with, so that
+    // signs, `0x` prefixes and zero padding are counted.
+    fn entry_width(&self, index: usize, v: &T, spec: Spec, plain: PlainFn<T>) -> usize {
+        let mut counter = Counter(0);
+        // Writing to a `Counter` cannot fail on its own; an element that
+        // reports an error is simply counted up to that point.
+        let _ = self.write_entry(&mut counter, index, v, spec, plain);
+        counter.0
+    }
+}
+
+// Formats one element into any writer with the options in a `Spec`. Used
+// when an element cannot be written to the formatter directly, since a
+// `Formatter` cannot be pointed at a second writer.
+type PlainFn<T> = fn(&T, Spec, &mut dyn Write) -> fmt::Result;
+
+// A copy of a formatter's options. The fill and alignment are applied by
+// `write_entry`; the rest are rebuilt by `write_spec!`.
+#[derive(Clone, Copy)]
+struct Spec {
+    width: Option<usize>,
+    precision: Option<usize>,
+    align: Option<fmt::Alignment>,
+    fill: char,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+}
+
+impl Spec {
+    fn of(f: &fmt::Formatter) -> Spec {
+        Spec {
+            width: f.width(),
+            precision: f.precision(),
+            align: f.align(),
+            fill: f.fill(),
+            plus: f.sign_plus(),
+            alternate: f.alternate(),
+            zero: f.sign_aware_zero_pad(),
+        }
+    }
+}
+
+// Indents everything after a line break by four spaces, so that an element
+// spanning several lines stays inside its list.
+struct Indented<W>(W);
+
+impl<W: Write> Write for Indented<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i != 0 {
+                self.0.write_str("\n    ")?;
+            }
+            self.0.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes `$v` with the width, precision and flags in `$spec`. Format strings
+// have to be literals, so there is one for each combination of `+`, `#` and
+// `0`; `$letter` is the trait's format letter.
+macro_rules! write_spec {
+    ($out:expr, $v:expr, $spec:expr, $letter:literal) => {
+        match ($spec.plus, $spec.alternate, $spec.zero) {
+            (false, false, false) => write_spec!($out, $v, $spec, "", $letter),
+            (true, false, false) => write_spec!($out, $v, $spec, "+", $letter),
+            (false, true, false) => write_spec!($out, $v, $spec, "#", $letter),
+            (false, false, true) => write_spec!($out, $v, $spec, "0", $letter),
+            (true, true, false) => write_spec!($out, $v, $spec, "+#", $letter),
+            (true, false, true) => write_spec!($out, $v, $spec, "+0", $letter),
+            (false, true, true) => write_spec!($out, $v, $spec, "#0", $letter),
+            (true, true, true) => write_spec!($out, $v, $spec, "+#0", $letter),
+        }
+    };
+    ($out:expr, $v:expr, $spec:expr, $flags:literal, $letter:literal) => {{
+        let w = $spec.width.unwrap_or(0);
+        match $spec.precision {
+            Some(p) => write!($out, concat!("{:", $flags, "w$.p$", $letter, "}"), $v, w = w, p = p),
+            None => write!($out, concat!("{:", $flags, "w$", $letter, "}"), $v, w = w),
+        }
+    }};
+}
+
+// A writer that only counts the characters it is given.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+// `… 999,990 more …`
+fn write_marker<W: Write>(out: &mut W, hidden: usize) -> fmt::Result {
+    out.write_str("… ")?;
+    let digits = hidden.to_string();
+    for (i, digit) in digits.chars().enumerate() {
+        if i != 0 && (digits.len() - i).is_multiple_of(3) {
+            out.write_char(',')?;
+        }
+        out.write_char(digit)?;
+    }
+    out.write_str(" more …")
+}
+
+fn marker_width(hidden: usize) -> usize {
+    let mut counter = Counter(0);
+    let _ = write_marker(&mut counter, hidden);
+    counter.0
+}
+
+// `List` and its adapter implement each formatting trait their elements do.
+// `$spec` is the trait's format letter, used to format elements into other
+// writers when they cannot be given the formatter.
+macro_rules! impl_fmt_traits {
+    ($($trait:ident $spec:literal),*) => {
+        $(
+            impl<T: fmt::$trait> fmt::$trait for ListDisplay<'_, T> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    self.write_with(f, <T as fmt::$trait>::fmt, |v, spec, out| {
+                        write_spec!(out, v, spec, $spec)
+                    })
+                }
+            }
+
+            impl<T: fmt::$trait> fmt::$trait for List<T> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::$trait::fmt(&self.display(), f)
+                }
+            }
+        )*
+    };
+}
+
+impl_fmt_traits!(
+    Display "",
+    LowerHex "x",
+    UpperHex "X",
+    Octal "o",
+    Binary "b",
+    LowerExp "e",
+    UpperExp "E"
+);
+
+// What went wrong while parsing a `List`. `E` is the element type's own
+// parse error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseListErrorKind<E> {
+    // The text does not start with `[`.
+    MissingOpenBracket,
+    // The input ended before the closing `]`.
+    MissingCloseBracket,
+    // Something other than whitespace follows the closing `]`.
+    TrailingCharacters,
+    // Nothing between two commas.
+    EmptyElement,
+    // The list uses `index: value` labels but this element has none.
+    MissingIndex,
+    // An index label that does not match the element's position.
+    IndexOutOfOrder { expected: usize, found: usize },
+    // An index label too large for a `usize`, so it can't be in order.
+    IndexTooLarge,
+    // The element text was rejected by the element type.
+    InvalidElement(E),
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseListError<E> {
+    kind: ParseListErrorKind<E>,
+    position: usize,
+}
+
+impl<E> ParseListError<E> {
+    pub fn kind(&self) -> &ParseListErrorKind<E> {
+        &self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ParseListError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseListErrorKind::MissingOpenBracket => write!(f, "expected `[`")?,
+            ParseListErrorKind::MissingCloseBracket => write!(f, "missing closing `]`")?,
+            ParseListErrorKind::TrailingCharacters => {
+                write!(f, "unexpected characters after the list")?
+            }
+            ParseListErrorKind::EmptyElement => write!(f, "empty element")?,
+            ParseListErrorKind::MissingIndex => write!(f, "element has no index label")?,
+            ParseListErrorKind::IndexOutOfOrder { expected, found } => {
+                write!(f, "index {} is out of order, expected {}", found, expected)?
+            }
+            ParseListErrorKind::IndexTooLarge => write!(f, "index label is too large")?,
+            ParseListErrorKind::InvalidElement(e) => write!(f, "invalid element ({})", e)?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl<E: Error + 'static> Error for ParseListError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ParseListErrorKind::InvalidElement(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Reads back what `Display` writes, `[0: 1, 1: 2, 2: 3]`, as well as the
+// unlabelled `[1, 2, 3]`. Whitespace is free between tokens and a trailing
+// comma is allowed, so the `{:#}` layout parses too. Commas and brackets
+// nested inside `[...]` belong to the element, which lets lists of lists
+// round-trip. The labelled form is chosen when the first element starts with
+// `<digits>:`, and then every element must carry the right label.
+//
+// Elements are not quoted, so one whose own text has a `,` or `]` outside
+// brackets does not read back: `List(vec!["a, b".to_string()])` prints as
+// `[0: a, b]`, which splits into `a` and an unlabelled `b`.
+impl<T: FromStr> FromStr for List<T> {
+    type Err = ParseListError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |kind, position| ParseListError { kind, position };
+        let skip_whitespace = |pos: usize| s.len() - s[pos..].trim_start().len();
+
+        let mut pos = skip_whitespace(0);
+        if !s[pos..].starts_with('[') {
+            return Err(error(ParseListErrorKind::MissingOpenBracket, pos));
+        }
+        pos += 1;
+
+        let mut items = Vec::new();
+        let mut labelled = None;
+
+        loop {
+            pos = skip_whitespace(pos);
+            if s[pos..].starts_with(']') {
+                pos += 1;
+                break;
+            }
+
+            // The element runs up to the next `,` or `]` outside any nested
+            // brackets.
+            let start = pos;
+            let mut depth = 0usize;
+            let end = s[start..]
+                .char_indices()
+                .find(|&(_, c)| match c {
+                    '[' => {
+                        depth += 1;
+                        false
+                    }
+                    ']' if depth > 0 => {
+                        depth -= 1;
+                        false
+                    }
+                    ']' => true,
+                    ',' => depth == 0,
+                    _ => false,
+                })
+                .map(|(i, _)| start + i)
+                .ok_or_else(|| error(ParseListErrorKind::MissingCloseBracket, s.len()))?;
+
+            let raw = s[start..end].trim_end();
+            if raw.is_empty() {
+                return Err(error(ParseListErrorKind::EmptyElement, start));
+            }
+
+            let label = index_label(raw);
+            let is_labelled = *labelled.get_or_insert(label.is_some());
+            let (value, value_start) = match (is_labelled, label) {
+                (false, _) => (raw, start),
+                (true, None) => return Err(error(ParseListErrorKind::MissingIndex, start)),
+                (true, Some((None, _))) => {
+                    return Err(error(ParseListErrorKind::IndexTooLarge, start))
+                }
+                (true, Some((Some(found), rest))) => {
+                    let expected = items.len();
+                    if found != expected {
+                        let kind = ParseListErrorKind::IndexOutOfOrder { expected, found };
+                        return Err(error(kind, start));
+                    }
+                    let value = rest.trim_start();
+                    (value, start + raw.len() - value.len())
+                }
+            };
+
+            let item = value
+                .parse()
+                .map_err(|e| error(ParseListErrorKind::InvalidElement(e), value_start))?;
+            items.push(item);
+
+            pos = end + 1;
+            if s[end..].starts_with(']') {
+                break;
+            }
+        }
+
+        pos = skip_whitespace(pos);
+        if pos != s.len() {
+            return Err(error(ParseListErrorKind::TrailingCharacters, pos));
+        }
+        Ok(List(items))
+    }
+}
+
+// Split `12: value` into the index and the text after the colon. The index
+// is `None` if it has the right shape but does not fit in a `usize`.
+fn index_label(element: &str) -> Option<(Option<usize>, &str)> {
+    let digits = element.len() - element.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    let rest = element[digits..].trim_start().strip_prefix(':')?;
+    Some((element[..digits].parse().ok(), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{List, ParseListErrorKind};
+
+    #[test]
+    fn nested_lists_are_indented() {
+        let nested = List(vec![List(vec![1, 2]), List(vec![3])]);
+        assert_eq!(
+            format!("{:#}", nested),
+            "[\n    0: [\n        0: 1,\n        1: 2,\n    ],\n    1: [\n        0: 3,\n    ],\n]"
+        );
+        assert_eq!(format!("{:#}", List(vec![List::<i32>(vec![])])), "[\n    0: [],\n]");
+    }
+
+    #[test]
+    fn multi_line_layout_keeps_element_flags() {
+        let list = List(vec![10, 255]);
+        assert_eq!(format!("{:#06x}", list), "[\n    0: 0x000a,\n    1: 0x00ff,\n]");
+        assert_eq!(format!("{:+#}", List(vec![1])), "[\n    0: +1,\n]");
+        assert_eq!(format!("{:*^#5}", List(vec!["ab"])), "[\n    0: *ab**,\n]");
+        assert_eq!(format!("{:#.1}", List(vec![0.25])), "[\n    0: 0.2,\n]");
+    }
+
+    #[test]
+    fn limits_that_cut_nothing_change_nothing() {
+        let list = List(vec![1, 2]);
+        for limited in [list.display().max_items(5), list.display().max_width(80)] {
+            assert_eq!(format!("{:+}", limited), "[0: +1, 1: +2]");
+            assert_eq!(format!("{:05}", limited), "[0: 00001, 1: 00002]");
+            assert_eq!(format!("{:#x}", limited), format!("{:#x}", list));
+        }
+    }
+
+    #[test]
+    fn elided_elements_keep_their_flags() {
+        let list = List((1..=10).collect::<Vec<i32>>());
+        let short = list.display().indices(false).max_items(2);
+        assert_eq!(format!("{:+}", short), "[+1, … 8 more …, +10]");
+        assert_eq!(format!("{:04}", short), "[0001, … 8 more …, 0010]");
+        // The signs count towards the width, so a third element won't fit.
+        let narrow = list.display().indices(false).max_width(24);
+        assert_eq!(format!("{:+}", narrow), "[+1, … 8 more …, +10]");
+    }
+
+    #[test]
+    fn elements_containing_separators_do_not_round_trip() {
+        let list = List(vec!["a, b".to_string()]);
+        let error = list.to_string().parse::<List<String>>().unwrap_err();
+        assert_eq!(error.kind(), &ParseListErrorKind::MissingIndex);
+        assert_eq!(error.position(), 7);
+        let unlabelled = list.display().indices(false).to_string();
+        assert_eq!(unlabelled.parse(), Ok(List(vec!["a".to_string(), "b".to_string()])));
+    }
+
+    #[test]
+    fn oversized_index_labels_are_reported_where_they_are() {
+        let error = "[0: 1, 99999999999999999999999: 2]".parse::<List<i32>>().unwrap_err();
+        assert_eq!(error.kind(), &ParseListErrorKind::IndexTooLarge);
+        assert_eq!(error.position(), 7);
+    }
+}
@@ -1,33 +1,71 @@
-use std::fmt;
-
-// Define a structure named `List` containing a `Vec`.
-struct List(Vec<i32>);
-
-impl fmt::Display for List {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Extract the value using tuple indexing,
-        // and create a reference to `vec`.
-        let vec = &self.0;
-
-        write!(f, "[")?;
-
-        // Iterate over `v` in `vec` while enumerating the iteration
-        // count in `count`.
-        for (count, v) in vec.iter().enumerate() {
-            // For every element except the first, add a comma.
-            // Use the ? operator to return on errors.
-            if count != 0 { write!(f, ", ")?; }
-            write!(f, "{}: {}", count, v)?;
-        }
-
-        // Close the opened bracket and return a fmt::Result value.
-        write!(f, "]")
-    }
-}
+// `List` and its formatting live in their own module.
+mod list;
 
+use list::{List, ParseListErrorKind};
 
 fn main() {
     let list = List(vec![1, 2, 3]);
     println!("{}", list);
-    println!("{:?}", vec![1, 2, 3])
+    println!("{:?}", vec![1, 2, 3]);
+
+    // Any `Display` element works, and formatter flags reach every element.
+    assert_eq!(format!("{:>3}", list), "[0:   1, 1:   2, 2:   3]");
+    assert_eq!(format!("{:x}", List(vec![10, 255])), "[0: a, 1: ff]");
+    assert_eq!(format!("{:08b}", List(vec![5u8])), "[0: 00000101]");
+    assert_eq!(format!("{:.1e}", List(vec![1500.0])), "[0: 1.5e3]");
+
+    let floats = List(vec![1.0, 2.5, -0.125]);
+    assert_eq!(format!("{:.2}", floats.display().indices(false)), "[1.00, 2.50, -0.12]");
+
+    let words = List(vec!["one", "two", "three"]);
+    println!("{:^7}", words);
+
+    // `{:#}` lays the list out one element per line, and is also passed on,
+    // so `{:#x}` prefixes each element with `0x`.
+    println!("{:#}", words.display().indices(false));
+    println!("{:#x}", List(vec![10, 255]));
+
+    // Huge lists can be elided in the middle. Only the elements that are
+    // shown are ever formatted.
+    let huge = List((1..=1_000_000).collect::<Vec<u32>>());
+    let short = huge.display().indices(false).max_items(10);
+    assert_eq!(
+        format!("{}", short),
+        "[1, 2, 3, 4, 5, … 999,990 more …, 999996, 999997, 999998, 999999, 1000000]"
+    );
+    let narrow = format!("{}", huge.display().max_width(60));
+    assert!(narrow.chars().count() <= 60);
+    println!("{}", narrow);
+    println!("{:>4x}", huge.display().indices(false).max_items(4));
+    println!("{:#}", huge.display().max_items(3));
+
+    // What `Display` writes reads back in, labelled or not, and in the
+    // multi-line layout too.
+    assert_eq!("[0: 1, 1: 2, 2: 3]".parse(), Ok(list.clone()));
+    assert_eq!("[1, 2, 3]".parse(), Ok(list.clone()));
+    for text in [
+        format!("{}", list),
+        format!("{:#}", list),
+        format!("{}", list.display().indices(false)),
+    ] {
+        assert_eq!(text.parse(), Ok(list.clone()));
+    }
+    let empty: List<i32> = List(vec![]);
+    assert_eq!(empty.to_string().parse(), Ok(empty));
+    let nested = List(vec![List(vec![1, 2]), List(vec![]), List(vec![3])]);
+    assert_eq!(nested.to_string().parse(), Ok(nested.clone()));
+    println!("{} reads back as {:?}", nested, nested);
+
+    // Errors say what went wrong and where.
+    let error = "[0: 1, 2: 2]".parse::<List<i32>>().unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ParseListErrorKind::IndexOutOfOrder { expected: 1, found: 2 }
+    );
+    assert_eq!(error.position(), 7);
+    for bad in ["1, 2]", "[0: 1, 2: 2]", "[0: 1, 1: x]", "[1, 2", "[1, , 2]"] {
+        if let Err(e) = bad.parse::<List<i32>>() {
+            println!("{:?}: {}", bad, e);
+        }
+    }
 }
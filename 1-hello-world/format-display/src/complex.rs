@@ -0,0 +1,661 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// A complex number `real + imag·i` stored in rectangular form.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub real: f64,
+    pub imag: f64,
+}
+
+impl Complex {
+    pub const ZERO: Complex = Complex { real: 0.0, imag: 0.0 };
+    pub const ONE: Complex = Complex { real: 1.0, imag: 0.0 };
+    pub const I: Complex = Complex { real: 0.0, imag: 1.0 };
+
+    pub const fn new(real: f64, imag: f64) -> Complex {
+        Complex { real, imag }
+    }
+
+    // Build a number from its modulus `r` and argument `theta` (radians).
+    pub fn from_polar(r: f64, theta: f64) -> Complex {
+        let (sin, cos) = theta.sin_cos();
+        Complex::new(r * cos, r * sin)
+    }
+
+    // Split the number into its modulus and argument, the inverse of
+    // `from_polar`.
+    pub fn to_polar(self) -> (f64, f64) {
+        (self.norm(), self.arg())
+    }
+
+    // The complex conjugate `real - imag·i`.
+    pub fn conj(self) -> Complex {
+        Complex::new(self.real, -self.imag)
+    }
+
+    // The modulus `|z|`. `hypot` avoids overflow for very large components.
+    pub fn norm(self) -> f64 {
+        self.real.hypot(self.imag)
+    }
+
+    // The squared modulus, cheaper than `norm` when only comparing sizes.
+    pub fn norm_sqr(self) -> f64 {
+        self.real * self.real + self.imag * self.imag
+    }
+
+    // The principal argument in `(-π, π]`.
+    pub fn arg(self) -> f64 {
+        self.imag.atan2(self.real)
+    }
+
+    pub fn recip(self) -> Complex {
+        Complex::ONE / self
+    }
+
+    pub fn is_finite(self) -> bool {
+        self.real.is_finite() && self.imag.is_finite()
+    }
+
+    pub fn is_nan(self) -> bool {
+        self.real.is_nan() || self.imag.is_nan()
+    }
+
+    // `e^z = e^real · (cos imag + i·sin imag)`
+    pub fn exp(self) -> Complex {
+        Complex::from_polar(self.real.exp(), self.imag)
+    }
+
+    // The principal natural logarithm `ln|z| + i·arg z`. The branch cut lies
+    // along the negative real axis, with the sign of a zero imaginary part
+    // choosing between `+πi` and `-πi`.
+    pub fn ln(self) -> Complex {
+        Complex::new(self.norm().ln(), self.arg())
+    }
+
+    // The principal square root, whose real part is never negative. It shares
+    // the branch cut of `ln`, so `sqrt(-4 ± 0i)` is `0 ± 2i`.
+    pub fn sqrt(self) -> Complex {
+        if self.real == 0.0 && self.imag == 0.0 {
+            return Complex::new(0.0, self.imag);
+        }
+        // Infinite parts follow C99's `csqrt`. They have to be caught before
+        // the division below, which would give `∞ / ∞ = NaN`.
+        if self.imag.is_infinite() {
+            return Complex::new(f64::INFINITY, self.imag);
+        }
+        if self.real == f64::INFINITY {
+            let imag = if self.imag.is_nan() { f64::NAN } else { 0.0f64.copysign(self.imag) };
+            return Complex::new(f64::INFINITY, imag);
+        }
+        if self.real == f64::NEG_INFINITY {
+            let real = if self.imag.is_nan() { f64::NAN } else { 0.0 };
+            return Complex::new(real, f64::INFINITY.copysign(self.imag));
+        }
+
+        // Near `f64::MAX`, `|z|` and the sum below would overflow. Taking
+        // `2·sqrt(z / 4)` instead is exact, since both factors are powers
+        // of two.
+        if self.real.abs() > f64::MAX / 4.0 || self.imag.abs() > f64::MAX / 4.0 {
+            return (self / 4.0).sqrt() * 2.0;
+        }
+
+        // Computed from `|z|` rather than through `exp(ln(z) / 2)` so that
+        // exact squares such as `-4` come back exact.
+        let t = ((self.real.abs() + self.norm()) / 2.0).sqrt();
+        if self.real >= 0.0 {
+            Complex::new(t, self.imag / (2.0 * t))
+        } else {
+            Complex::new(self.imag.abs() / (2.0 * t), t.copysign(self.imag))
+        }
+    }
+
+    // `z^exp` for a real exponent, taken on the principal branch.
+    pub fn powf(self, exp: f64) -> Complex {
+        if exp == 0.0 {
+            return Complex::ONE;
+        }
+        if self == Complex::ZERO {
+            return if exp > 0.0 {
+                Complex::ZERO
+            } else {
+                Complex::new(f64::INFINITY, 0.0)
+            };
+        }
+
+        let (r, theta) = self.to_polar();
+        Complex::from_polar(r.powf(exp), theta * exp)
+    }
+
+    // `z^exp = e^(exp · ln z)` for a complex exponent, taken on the principal
+    // branch.
+    pub fn powc(self, exp: Complex) -> Complex {
+        if exp == Complex::ZERO {
+            return Complex::ONE;
+        }
+        if self == Complex::ZERO {
+            // `0^w` is only defined when the real part of `w` is positive.
+            return if exp.real > 0.0 {
+                Complex::ZERO
+            } else {
+                Complex::new(f64::NAN, f64::NAN)
+            };
+        }
+
+        (exp * self.ln()).exp()
+    }
+
+    // `z^n` for an integer exponent by repeated squaring, which stays exact
+    // for Gaussian integers where `powf` would pick up rounding error.
+    pub fn powi(self, n: i32) -> Complex {
+        let mut base = if n < 0 { self.recip() } else { self };
+        let mut n = n.unsigned_abs();
+        let mut acc = Complex::ONE;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                acc *= base;
+            }
+            base *= base;
+            n >>= 1;
+        }
+        acc
+    }
+
+    // Whether two numbers lie within `epsilon` of each other in the plane.
+    pub fn approx_eq(self, other: Complex, epsilon: f64) -> bool {
+        (self - other).norm() <= epsilon
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(real: f64) -> Self {
+        Complex::new(real, 0.0)
+    }
+}
+
+// Prints `a + bi` or `a - bi`, picking the operator from the sign of the
+// imaginary part so that the output reads back through `FromStr`. A precision
+// such as `{:.3}` applies to both parts, and the alternate flag `{:#}` switches
+// to the polar form `r∠θ` with `θ` in radians.
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let (r, theta) = self.to_polar();
+            return match f.precision() {
+                Some(p) => write!(f, "{:.*}∠{:.*}", p, r, p, theta),
+                None => write!(f, "{}∠{}", r, theta),
+            };
+        }
+
+        let sign = if self.imag.is_sign_negative() { '-' } else { '+' };
+        match f.precision() {
+            Some(p) => write!(f, "{:.*} {} {:.*}i", p, self.real, sign, p, self.imag.abs()),
+            None => write!(f, "{} {} {}i", self.real, sign, self.imag.abs()),
+        }
+    }
+}
+
+// What went wrong while parsing a `Complex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseComplexErrorKind {
+    // The input was empty or only whitespace.
+    Empty,
+    // Something that started like a number could not be read as one.
+    InvalidNumber,
+    // A character that does not fit the grammar at this point.
+    UnexpectedChar(char),
+    // The input stopped in the middle of a number.
+    UnexpectedEnd,
+    // Both terms of `a ± b` were real, or both were imaginary.
+    DuplicatePart,
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseComplexError {
+    kind: ParseComplexErrorKind,
+    position: usize,
+}
+
+impl ParseComplexError {
+    pub fn kind(&self) -> ParseComplexErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseComplexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseComplexErrorKind::Empty => {
+                return write!(f, "cannot parse a complex number from an empty string")
+            }
+            ParseComplexErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ParseComplexErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c)?,
+            ParseComplexErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseComplexErrorKind::DuplicatePart => {
+                write!(f, "expected one real and one imaginary part")?
+            }
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseComplexError {}
+
+// Accepts the rectangular forms `3-2i`, `-i`, `4`, `2.5e3+1e-2i` (whitespace
+// around the operator is allowed, as `Display` writes it), the pair `(1, 2)`
+// and the polar form `r∠θ` written by `{:#}`. A sign that belongs to a number
+// must touch it: `3 + -2i` reads as `3 - 2i`, but `3 + - 2i` and `- 2i` are
+// rejected.
+impl FromStr for Complex {
+    type Err = ParseComplexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { src: s, pos: 0 };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Err(parser.error(ParseComplexErrorKind::Empty));
+        }
+
+        let value = if parser.eat('(') {
+            parser.pair()?
+        } else {
+            parser.rectangular_or_polar()?
+        };
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.unexpected()),
+        }
+    }
+}
+
+// One half of a rectangular number.
+enum Term {
+    Real(f64),
+    Imag(f64),
+}
+
+// A hand-rolled cursor over the input. Positions are byte offsets so they can
+// be used to slice the original string.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, kind: ParseComplexErrorKind) -> ParseComplexError {
+        ParseComplexError { kind, position: self.pos }
+    }
+
+    fn unexpected(&self) -> ParseComplexError {
+        match self.peek() {
+            Some(c) => self.error(ParseComplexErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseComplexErrorKind::UnexpectedEnd),
+        }
+    }
+
+    // `(real, imag)`, with the opening parenthesis already consumed.
+    fn pair(&mut self) -> Result<Complex, ParseComplexError> {
+        self.skip_whitespace();
+        let real = self.signed_number()?;
+        self.skip_whitespace();
+        if !self.eat(',') {
+            return Err(self.unexpected());
+        }
+        self.skip_whitespace();
+        let imag = self.signed_number()?;
+        self.skip_whitespace();
+        if !self.eat(')') {
+            return Err(self.unexpected());
+        }
+        Ok(Complex::new(real, imag))
+    }
+
+    fn rectangular_or_polar(&mut self) -> Result<Complex, ParseComplexError> {
+        let sign = self.sign().unwrap_or(1.0);
+        let first = self.term(sign)?;
+        self.skip_whitespace();
+
+        if let Term::Real(r) = first {
+            if self.eat('∠') {
+                self.skip_whitespace();
+                return Ok(Complex::from_polar(r, self.signed_number()?));
+            }
+        }
+
+        let Some(mut sign) = self.sign() else {
+            return Ok(match first {
+                Term::Real(real) => Complex::new(real, 0.0),
+                Term::Imag(imag) => Complex::new(0.0, imag),
+            });
+        };
+        self.skip_whitespace();
+        // Tolerate a second sign so that `3 + -2i` still reads as `3 - 2i`.
+        // Like the first, it may not be followed by whitespace.
+        sign *= self.sign().unwrap_or(1.0);
+
+        let second_pos = self.pos;
+        match (first, self.term(sign)?) {
+            (Term::Real(real), Term::Imag(imag)) | (Term::Imag(imag), Term::Real(real)) => {
+                Ok(Complex::new(real, imag))
+            }
+            _ => Err(ParseComplexError {
+                kind: ParseComplexErrorKind::DuplicatePart,
+                position: second_pos,
+            }),
+        }
+    }
+
+    // A number optionally followed by the unit `i`, or the unit on its own.
+    // The sign is applied by negation so that `-0` keeps its sign bit.
+    fn term(&mut self, sign: f64) -> Result<Term, ParseComplexError> {
+        let magnitude = self.number()?;
+        let apply = |x: f64| if sign < 0.0 { -x } else { x };
+
+        match magnitude {
+            Some(x) if self.eat('i') => Ok(Term::Imag(apply(x))),
+            Some(x) => Ok(Term::Real(apply(x))),
+            None if self.eat('i') => Ok(Term::Imag(apply(1.0))),
+            None => Err(self.unexpected()),
+        }
+    }
+
+    fn sign(&mut self) -> Option<f64> {
+        if self.eat('+') {
+            Some(1.0)
+        } else if self.eat('-') {
+            Some(-1.0)
+        } else {
+            None
+        }
+    }
+
+    fn signed_number(&mut self) -> Result<f64, ParseComplexError> {
+        let negative = self.sign() == Some(-1.0);
+        match self.number()? {
+            Some(x) if negative => Ok(-x),
+            Some(x) => Ok(x),
+            None => Err(self.unexpected()),
+        }
+    }
+
+    // An unsigned decimal number with optional fraction and exponent, or one
+    // of the special values `inf`, `infinity` and `NaN` that `f64` prints.
+    // Returns `None` without consuming anything if no number starts here.
+    fn number(&mut self) -> Result<Option<f64>, ParseComplexError> {
+        let start = self.pos;
+
+        for word in ["infinity", "inf", "nan"] {
+            let matches = self
+                .rest()
+                .get(..word.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(word));
+            if matches {
+                self.pos += word.len();
+                return Ok(Some(if word == "nan" { f64::NAN } else { f64::INFINITY }));
+            }
+        }
+
+        let digits = |p: &mut Self| {
+            let rest = p.rest();
+            let n = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            p.pos += n;
+            n
+        };
+
+        let mut mantissa = digits(self);
+        if self.eat('.') {
+            mantissa += digits(self);
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        if mantissa == 0 {
+            self.pos = start;
+            return Err(self.error(ParseComplexErrorKind::InvalidNumber));
+        }
+
+        // Only treat `e` as an exponent if digits follow, so that a stray `e`
+        // is reported where it stands rather than swallowed into the number.
+        let before_exponent = self.pos;
+        if self.eat('e') || self.eat('E') {
+            let _ = self.sign();
+            if digits(self) == 0 {
+                self.pos = before_exponent;
+            }
+        }
+
+        self.src[start..self.pos]
+            .parse()
+            .map(Some)
+            .map_err(|_| ParseComplexError {
+                kind: ParseComplexErrorKind::InvalidNumber,
+                position: start,
+            })
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex::new(-self.real, -self.imag)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.real + rhs.real, self.imag + rhs.imag)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.real - rhs.real, self.imag - rhs.imag)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(
+            self.real * rhs.real - self.imag * rhs.imag,
+            self.real * rhs.imag + self.imag * rhs.real,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    // Smith's algorithm: scaling by the larger component of the divisor keeps
+    // the intermediate products from overflowing or underflowing.
+    fn div(self, rhs: Complex) -> Complex {
+        let (a, b, c, d) = (self.real, self.imag, rhs.real, rhs.imag);
+
+        if c.abs() >= d.abs() {
+            let ratio = d / c;
+            let denom = c + d * ratio;
+            Complex::new((a + b * ratio) / denom, (b - a * ratio) / denom)
+        } else {
+            let ratio = c / d;
+            let denom = c * ratio + d;
+            Complex::new((a * ratio + b) / denom, (b * ratio - a) / denom)
+        }
+    }
+}
+
+// Mixed operations with a real number treat it as `x + 0i`, but skip the
+// zero terms so that infinities and signed zeros survive.
+impl Add<f64> for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: f64) -> Complex {
+        Complex::new(self.real + rhs, self.imag)
+    }
+}
+
+impl Sub<f64> for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: f64) -> Complex {
+        Complex::new(self.real - rhs, self.imag)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: f64) -> Complex {
+        Complex::new(self.real * rhs, self.imag * rhs)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: f64) -> Complex {
+        Complex::new(self.real / rhs, self.imag / rhs)
+    }
+}
+
+impl Add<Complex> for f64 {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self + rhs.real, rhs.imag)
+    }
+}
+
+impl Sub<Complex> for f64 {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self - rhs.real, -rhs.imag)
+    }
+}
+
+impl Mul<Complex> for f64 {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(self * rhs.real, self * rhs.imag)
+    }
+}
+
+impl Div<Complex> for f64 {
+    type Output = Complex;
+
+    fn div(self, rhs: Complex) -> Complex {
+        Complex::from(self) / rhs
+    }
+}
+
+// The compound assignment operators all forward to the binary ones above.
+macro_rules! forward_assign_op {
+    ($($trait:ident $method:ident $op:tt $rhs:ty;)*) => {
+        $(
+            impl $trait<$rhs> for Complex {
+                fn $method(&mut self, rhs: $rhs) {
+                    *self = *self $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+forward_assign_op! {
+    AddAssign add_assign + Complex;
+    SubAssign sub_assign - Complex;
+    MulAssign mul_assign * Complex;
+    DivAssign div_assign / Complex;
+    AddAssign add_assign + f64;
+    SubAssign sub_assign - f64;
+    MulAssign mul_assign * f64;
+    DivAssign div_assign / f64;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::{Complex, ParseComplexErrorKind};
+
+    #[test]
+    fn sqrt_of_infinite_parts() {
+        let (inf, nan) = (f64::INFINITY, f64::NAN);
+        assert_eq!(Complex::new(inf, inf).sqrt(), Complex::new(inf, inf));
+        assert_eq!(Complex::new(1.0, -inf).sqrt(), Complex::new(inf, -inf));
+        assert_eq!(Complex::new(nan, inf).sqrt(), Complex::new(inf, inf));
+        assert_eq!(Complex::new(-inf, inf).sqrt(), Complex::new(inf, inf));
+        assert_eq!(Complex::new(inf, 2.0).sqrt(), Complex::new(inf, 0.0));
+        assert_eq!(Complex::new(-inf, -2.0).sqrt(), Complex::new(0.0, -inf));
+        let z = Complex::new(inf, nan).sqrt();
+        assert!(z.real == inf && z.imag.is_nan());
+    }
+
+    #[test]
+    fn sqrt_near_the_largest_finite_values() {
+        let big = 1.7e308;
+        let z = Complex::new(big, big).sqrt();
+        assert!(z.is_finite(), "{}", z);
+        // `(a + ai)` is `a·√2·e^(iπ/4)`, so its root is `√a·2^(1/4)·e^(iπ/8)`.
+        let expected = Complex::from_polar(big.sqrt() * 2f64.powf(0.25), PI / 8.0);
+        assert!(z.approx_eq(expected, 1e-15 * expected.norm()), "{} vs {}", z, expected);
+        assert_eq!(Complex::new(-f64::MAX, 0.0).sqrt(), Complex::new(0.0, f64::MAX.sqrt()));
+        let w = Complex::new(f64::MAX, -f64::MAX).sqrt();
+        assert!(w.is_finite() && w.real > 0.0 && w.imag < 0.0, "{}", w);
+    }
+
+    #[test]
+    fn signs_must_touch_their_numbers() {
+        assert_eq!("3 + -2i".parse(), Ok(Complex::new(3.0, -2.0)));
+        assert_eq!("3 - -2i".parse(), Ok(Complex::new(3.0, 2.0)));
+        for (text, position) in [("3 + - 2i", 5), ("- 2i", 1)] {
+            let error = text.parse::<Complex>().unwrap_err();
+            assert_eq!(error.kind(), ParseComplexErrorKind::UnexpectedChar(' '), "{}", text);
+            assert_eq!(error.position(), position, "{}", text);
+        }
+    }
+}
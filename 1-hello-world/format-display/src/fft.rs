@@ -0,0 +1,237 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::f64::consts::PI;
+
+use crate::complex::Complex;
+
+// Forward discrete Fourier transform in place, `X[k] = Σ x[n]·e^(-2πikn/N)`.
+// Powers of two use the iterative radix-2 algorithm directly; every other
+// length goes through Bluestein's chirp-z transform, which is still
+// `O(N log N)` but allocates scratch space.
+pub fn fft(data: &mut [Complex]) {
+    transform(data, false);
+}
+
+// Inverse transform in place, including the `1/N` scaling, so that
+// `ifft` undoes `fft`.
+pub fn ifft(data: &mut [Complex]) {
+    transform(data, true);
+    let scale = data.len() as f64;
+    for x in data.iter_mut() {
+        *x /= scale;
+    }
+}
+
+// The spectrum of a real signal. Because the input is real the spectrum is
+// conjugate-symmetric, so only the `N/2 + 1` non-redundant bins are returned.
+pub fn rfft(input: &[f64]) -> Vec<Complex> {
+    let mut data: Vec<Complex> = input.iter().map(|&x| Complex::from(x)).collect();
+    fft(&mut data);
+    data.truncate(input.len() / 2 + 1);
+    data
+}
+
+// Rebuild a real signal of length `n` from the half spectrum produced by
+// `rfft`, filling in the missing bins from conjugate symmetry.
+pub fn irfft(spectrum: &[Complex], n: usize) -> Vec<f64> {
+    let mut data = vec![Complex::ZERO; n];
+    for (k, slot) in data.iter_mut().enumerate() {
+        *slot = if k < spectrum.len() {
+            spectrum[k]
+        } else {
+            spectrum.get(n - k).map_or(Complex::ZERO, |c| c.conj())
+        };
+    }
+    ifft(&mut data);
+    data.into_iter().map(|c| c.real).collect()
+}
+
+// Linear convolution `(a * b)[n] = Σ a[k]·b[n - k]`, computed by multiplying
+// zero-padded spectra. The result has `a.len() + b.len() - 1` elements.
+pub fn convolve(a: &[Complex], b: &[Complex]) -> Vec<Complex> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+
+    let mut fa = a.to_vec();
+    fa.resize(size, Complex::ZERO);
+    let mut fb = b.to_vec();
+    fb.resize(size, Complex::ZERO);
+
+    fft(&mut fa);
+    fft(&mut fb);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= *y;
+    }
+    ifft(&mut fa);
+
+    fa.truncate(len);
+    fa
+}
+
+// Convolution of two real sequences.
+pub fn convolve_real(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let to_complex = |xs: &[f64]| xs.iter().map(|&x| Complex::from(x)).collect::<Vec<_>>();
+    convolve(&to_complex(a), &to_complex(b))
+        .into_iter()
+        .map(|c| c.real)
+        .collect()
+}
+
+// The textbook `O(N²)` transform, kept as a reference to check the fast
+// versions against.
+pub fn dft(input: &[Complex]) -> Vec<Complex> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .fold(Complex::ZERO, |acc, (j, &x)| acc + x * twiddle(k * j % n, n, false))
+        })
+        .collect()
+}
+
+// `e^(∓2πi·k/n)`, with the sign flipped for the inverse direction.
+fn twiddle(k: usize, n: usize, inverse: bool) -> Complex {
+    let sign = if inverse { 1.0 } else { -1.0 };
+    Complex::from_polar(1.0, sign * 2.0 * PI * k as f64 / n as f64)
+}
+
+// Unscaled transform in either direction.
+fn transform(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        radix2(data, inverse);
+    } else {
+        bluestein(data, inverse);
+    }
+}
+
+// Iterative Cooley–Tukey: reorder into bit-reversed positions, then combine
+// butterflies of size 2, 4, 8, ... in place.
+fn radix2(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let bits = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    // Twiddles are computed once for the full length and strided for the
+    // smaller stages, rather than built up by repeated multiplication which
+    // would accumulate rounding error.
+    let twiddles: Vec<Complex> = (0..n / 2).map(|k| twiddle(k, n, inverse)).collect();
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let even = data[start + k];
+                let odd = data[start + k + half] * twiddles[k * stride];
+                data[start + k] = even + odd;
+                data[start + k + half] = even - odd;
+            }
+        }
+        len *= 2;
+    }
+}
+
+// Bluestein's algorithm rewrites the DFT as a convolution with the chirp
+// `e^(∓πi·k²/n)`, and that convolution is done with power-of-two transforms.
+fn bluestein(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let m = (2 * n - 1).next_power_of_two();
+    let sign = if inverse { 1.0 } else { -1.0 };
+
+    // `k²` is reduced modulo `2n` first; the chirp has that period and the
+    // reduction keeps the angle small enough to stay accurate for large `k`.
+    let chirp: Vec<Complex> = (0..n)
+        .map(|k| {
+            let k2 = (k as u128 * k as u128 % (2 * n as u128)) as f64;
+            Complex::from_polar(1.0, sign * PI * k2 / n as f64)
+        })
+        .collect();
+
+    let mut a = vec![Complex::ZERO; m];
+    for k in 0..n {
+        a[k] = data[k] * chirp[k];
+    }
+
+    let mut b = vec![Complex::ZERO; m];
+    b[0] = chirp[0].conj();
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+
+    radix2(&mut a, false);
+    radix2(&mut b, false);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x *= *y;
+    }
+    radix2(&mut a, true);
+
+    for k in 0..n {
+        data[k] = a[k] * chirp[k] / m as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dft, fft, ifft};
+    use crate::complex::Complex;
+
+    fn signal(n: usize) -> Vec<Complex> {
+        (0..n)
+            .map(|k| Complex::new((k as f64 * 0.7).sin(), (k as f64 * 1.3).cos()))
+            .collect()
+    }
+
+    // Checks `fft` against the naive DFT, and `ifft` against the input.
+    fn check(n: usize) {
+        let signal = signal(n);
+        let expected = dft(&signal);
+
+        let mut spectrum = signal.clone();
+        fft(&mut spectrum);
+        let error = spectrum
+            .iter()
+            .zip(&expected)
+            .map(|(a, b)| (*a - *b).norm())
+            .fold(0.0, f64::max);
+        assert!(error < 1e-9, "n = {}: error {}", n, error);
+
+        ifft(&mut spectrum);
+        for (a, b) in spectrum.iter().zip(&signal) {
+            assert!(a.approx_eq(*b, 1e-12), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn power_of_two_lengths_match_dft() {
+        for n in [1, 2, 4, 8, 64, 256] {
+            check(n);
+        }
+    }
+
+    #[test]
+    fn bluestein_lengths_match_dft() {
+        for n in [3, 5, 12, 97, 100, 255] {
+            check(n);
+        }
+    }
+}
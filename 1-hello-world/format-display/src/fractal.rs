@@ -0,0 +1,258 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::f64::consts::LN_2;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::thread;
+
+use formatting::color::Color;
+use formatting::ppm::Image;
+
+use crate::complex::Complex;
+
+// Points escape once `|z|` passes this radius. A radius much larger than the
+// minimal 2 makes the smooth iteration count below nearly exact.
+const BAILOUT_SQR: f64 = 256.0 * 256.0;
+
+// Which iteration `z ← z² + c` to run. For the Mandelbrot set `c` is the pixel
+// and `z` starts at zero; for a Julia set `c` is fixed and `z` starts at the
+// pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fractal {
+    Mandelbrot,
+    Julia(Complex),
+}
+
+impl Fractal {
+    // A view of the whole set at `width_px × height_px`. The Mandelbrot set
+    // leans to the left of the origin; Julia sets are symmetric about it.
+    pub fn default_viewport(self, width_px: usize, height_px: usize) -> Viewport {
+        let (center, width) = match self {
+            Fractal::Mandelbrot => (Complex::new(-0.75, 0.0), 3.5),
+            Fractal::Julia(_) => (Complex::ZERO, 3.2),
+        };
+        Viewport::centered(center, width, width_px, height_px)
+    }
+}
+
+// The rectangle of the complex plane that is drawn, from the bottom-left
+// corner `min` to the top-right corner `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub min: Complex,
+    pub max: Complex,
+}
+
+impl Viewport {
+    // A viewport of the given width centred on `center`, with the height
+    // chosen so that pixels come out square at `width_px × height_px`.
+    pub fn centered(center: Complex, width: f64, width_px: usize, height_px: usize) -> Viewport {
+        let height = width * height_px as f64 / width_px as f64;
+        let half = Complex::new(width / 2.0, height / 2.0);
+        Viewport {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    // The point under the centre of pixel `(x, y)`, with row 0 at the top.
+    fn point(&self, x: usize, y: usize, width_px: usize, height_px: usize) -> Complex {
+        let span = self.max - self.min;
+        Complex::new(
+            self.min.real + span.real * (x as f64 + 0.5) / width_px as f64,
+            self.max.imag - span.imag * (y as f64 + 0.5) / height_px as f64,
+        )
+    }
+}
+
+// A gradient through evenly spaced colour stops, plus the colour used for
+// points that never escape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorMap {
+    stops: Vec<Color>,
+    interior: Color,
+}
+
+impl ColorMap {
+    pub fn new(stops: Vec<Color>) -> ColorMap {
+        assert!(!stops.is_empty(), "a colour map needs at least one stop");
+        ColorMap {
+            stops,
+            interior: Color { red: 0, green: 0, blue: 0 },
+        }
+    }
+
+    pub fn interior(self, interior: Color) -> ColorMap {
+        ColorMap { interior, ..self }
+    }
+
+    // The colour at `t`, clamped to `[0, 1]`, interpolating each channel
+    // linearly between the two surrounding stops.
+    pub fn sample(&self, t: f64) -> Color {
+        let last = self.stops.len() - 1;
+        let pos = t.clamp(0.0, 1.0) * last as f64;
+        let i = (pos.floor() as usize).min(last.saturating_sub(1));
+        let (from, to) = (self.stops[i], self.stops[(i + 1).min(last)]);
+        let frac = pos - i as f64;
+
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+        Color {
+            red: lerp(from.red, to.red),
+            green: lerp(from.green, to.green),
+            blue: lerp(from.blue, to.blue),
+        }
+    }
+}
+
+impl Default for ColorMap {
+    // Deep blue through white to orange, the familiar escape-time palette.
+    fn default() -> Self {
+        ColorMap::new(vec![
+            Color { red: 0, green: 7, blue: 100 },
+            Color { red: 32, green: 107, blue: 203 },
+            Color { red: 237, green: 255, blue: 255 },
+            Color { red: 255, green: 170, blue: 0 },
+            Color { red: 0, green: 2, blue: 0 },
+        ])
+    }
+}
+
+// Escape-time renderer. Rows are handed out one at a time to a pool of scoped
+// threads, so the slow rows through the middle of the set do not leave the
+// other workers idle.
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    pub fractal: Fractal,
+    pub viewport: Viewport,
+    pub width: usize,
+    pub height: usize,
+    pub max_iterations: u32,
+    pub color_map: ColorMap,
+    pub threads: usize,
+    // Whether `viewport` was chosen by the caller rather than defaulted from
+    // the fractal.
+    viewport_set: bool,
+}
+
+impl Renderer {
+    // A renderer for the whole Mandelbrot set at the given resolution, using
+    // every available core.
+    pub fn new(width: usize, height: usize) -> Renderer {
+        Renderer {
+            fractal: Fractal::Mandelbrot,
+            viewport: Fractal::Mandelbrot.default_viewport(width, height),
+            width,
+            height,
+            max_iterations: 256,
+            color_map: ColorMap::default(),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            viewport_set: false,
+        }
+    }
+
+    // Switching fractal also moves the view to that fractal's default, unless
+    // a viewport has been set explicitly.
+    pub fn fractal(self, fractal: Fractal) -> Renderer {
+        let viewport = if self.viewport_set {
+            self.viewport
+        } else {
+            fractal.default_viewport(self.width, self.height)
+        };
+        Renderer { fractal, viewport, ..self }
+    }
+
+    pub fn viewport(self, viewport: Viewport) -> Renderer {
+        Renderer { viewport, viewport_set: true, ..self }
+    }
+
+    pub fn max_iterations(self, max_iterations: u32) -> Renderer {
+        Renderer { max_iterations, ..self }
+    }
+
+    pub fn color_map(self, color_map: ColorMap) -> Renderer {
+        Renderer { color_map, ..self }
+    }
+
+    pub fn threads(self, threads: usize) -> Renderer {
+        Renderer { threads, ..self }
+    }
+
+    pub fn render(&self) -> Image {
+        let mut pixels = vec![self.color_map.interior; self.width * self.height];
+
+        if self.width > 0 {
+            let rows = Mutex::new(pixels.chunks_mut(self.width).enumerate());
+            thread::scope(|scope| {
+                for _ in 0..self.threads.max(1) {
+                    scope.spawn(|| loop {
+                        // Hold the lock only long enough to claim a row.
+                        let Some((y, row)) = rows.lock().unwrap().next() else {
+                            break;
+                        };
+                        self.render_row(y, row);
+                    });
+                }
+            });
+        }
+
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+
+    fn render_row(&self, y: usize, row: &mut [Color]) {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            let point = self.viewport.point(x, y, self.width, self.height);
+            if let Some(mu) = self.escape_time(point) {
+                // Log scaling spreads the colours evenly over both the
+                // quickly and slowly escaping regions.
+                let t = (mu + 1.0).ln() / (self.max_iterations as f64 + 1.0).ln();
+                *pixel = self.color_map.sample(t);
+            }
+        }
+    }
+
+    // The smooth (fractional) iteration count at which `point` escapes, or
+    // `None` if it is still bounded after `max_iterations`.
+    pub fn escape_time(&self, point: Complex) -> Option<f64> {
+        let (mut z, c) = match self.fractal {
+            Fractal::Mandelbrot => (Complex::ZERO, point),
+            Fractal::Julia(c) => (point, c),
+        };
+
+        for n in 0..self.max_iterations {
+            let norm_sqr = z.norm_sqr();
+            if norm_sqr > BAILOUT_SQR {
+                // Subtract how far past the bailout `z` overshot, measured in
+                // doublings of `log |z|`, to remove the banding between
+                // integer counts.
+                let log_norm = norm_sqr.ln() / 2.0;
+                let nu = (log_norm / LN_2).ln() / LN_2;
+                return Some((n as f64 + 1.0 - nu).max(0.0));
+            }
+            z = z * z + c;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fractal, Renderer, Viewport};
+    use crate::complex::Complex;
+
+    #[test]
+    fn switching_fractal_moves_the_default_viewport() {
+        let c = Complex::new(-0.8, 0.156);
+        let julia = Renderer::new(480, 320).fractal(Fractal::Julia(c));
+        assert_eq!(julia.viewport, Viewport::centered(Complex::ZERO, 3.2, 480, 320));
+
+        let view = Viewport::centered(Complex::new(0.3, 0.0), 0.5, 480, 320);
+        let zoomed = Renderer::new(480, 320).viewport(view).fractal(Fractal::Julia(c));
+        assert_eq!(zoomed.viewport, view);
+    }
+}
@@ -0,0 +1,225 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+
+// A structure holding two numbers. `Debug` will be derived so the results can
+// be contrasted with `Display`.
+//
+// The two numbers are the bounds of the closed interval `[min, max]`. The
+// fields are private so that `min <= max` always holds; build one with
+// `MinMax::new` or `MinMax::from_iter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MinMax(i64, i64);
+
+// Implement `Display` for `MinMax`.
+impl fmt::Display for MinMax {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Use `self.number` to refer to each positional data point.
+        write!(f, "{}, {}", self.0, self.1)
+    }
+}
+
+// Returned by `MinMax::new` when the bounds are the wrong way round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRange {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl fmt::Display for InvalidRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "min {} is greater than max {}", self.min, self.max)
+    }
+}
+
+impl Error for InvalidRange {}
+
+impl MinMax {
+    pub fn new(min: i64, max: i64) -> Result<MinMax, InvalidRange> {
+        if min <= max {
+            Ok(MinMax(min, max))
+        } else {
+            Err(InvalidRange { min, max })
+        }
+    }
+
+    // The interval holding a single value.
+    pub fn point(value: i64) -> MinMax {
+        MinMax(value, value)
+    }
+
+    // The tightest interval around every value, found in a single pass, or
+    // `None` if there are no values.
+    pub fn from_iter<I: IntoIterator<Item = i64>>(values: I) -> Option<MinMax> {
+        values.into_iter().fold(None, |bounds, x| match bounds {
+            None => Some(MinMax(x, x)),
+            Some(MinMax(min, max)) => Some(MinMax(min.min(x), max.max(x))),
+        })
+    }
+
+    pub fn min(self) -> i64 {
+        self.0
+    }
+
+    pub fn max(self) -> i64 {
+        self.1
+    }
+
+    pub fn contains(self, value: i64) -> bool {
+        self.0 <= value && value <= self.1
+    }
+
+    // The number of integers in the interval, both ends included. The full
+    // `i64` range holds one more than `u64::MAX`, so that one case saturates.
+    pub fn len(self) -> u64 {
+        (self.1 as i128 - self.0 as i128 + 1).min(u64::MAX as i128) as u64
+    }
+
+    pub fn overlaps(self, other: MinMax) -> bool {
+        self.0 <= other.1 && other.0 <= self.1
+    }
+
+    // The values in both intervals, if there are any.
+    pub fn intersect(self, other: MinMax) -> Option<MinMax> {
+        MinMax::new(self.0.max(other.0), self.1.min(other.1)).ok()
+    }
+
+    // The smallest interval covering both, including any gap between them.
+    pub fn hull(self, other: MinMax) -> MinMax {
+        MinMax(self.0.min(other.0), self.1.max(other.1))
+    }
+
+    // The nearest value to `value` inside the interval.
+    pub fn clamp(self, value: i64) -> i64 {
+        value.clamp(self.0, self.1)
+    }
+
+    // Split into `[min, at - 1]` and `[at, max]`, like `slice::split_at`.
+    // Returns `None` unless both halves would be non-empty.
+    pub fn split_at(self, at: i64) -> Option<(MinMax, MinMax)> {
+        if self.0 < at && at <= self.1 {
+            Some((MinMax(self.0, at - 1), MinMax(at, self.1)))
+        } else {
+            None
+        }
+    }
+}
+
+// A set of integers stored as sorted, disjoint `MinMax` ranges. Ranges that
+// overlap or merely touch (`[1, 3]` and `[4, 6]`) are merged on insertion, so
+// every set has exactly one representation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    ranges: Vec<MinMax>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    // The number of disjoint ranges, not the number of integers.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = MinMax> + '_ {
+        self.ranges.iter().copied()
+    }
+
+    // The indices of the stored ranges lying within `slack` of `range`.
+    fn touching(&self, range: MinMax, slack: i64) -> std::ops::Range<usize> {
+        let start = self
+            .ranges
+            .partition_point(|r| r.1 < range.0.saturating_sub(slack));
+        let end = self
+            .ranges
+            .partition_point(|r| r.0 <= range.1.saturating_add(slack));
+        start..end
+    }
+
+    pub fn insert(&mut self, range: MinMax) {
+        let touching = self.touching(range, 1);
+        let merged = if touching.is_empty() {
+            range
+        } else {
+            range
+                .hull(self.ranges[touching.start])
+                .hull(self.ranges[touching.end - 1])
+        };
+        self.ranges.splice(touching, [merged]);
+    }
+
+    pub fn remove(&mut self, range: MinMax) {
+        let overlapping = self.touching(range, 0);
+        if overlapping.is_empty() {
+            return;
+        }
+
+        // Only the first and last overlapping ranges can stick out past
+        // `range`; whatever sticks out survives.
+        let first = self.ranges[overlapping.start];
+        let last = self.ranges[overlapping.end - 1];
+        let mut kept = Vec::with_capacity(2);
+        if first.0 < range.0 {
+            kept.push(MinMax(first.0, range.0 - 1));
+        }
+        if last.1 > range.1 {
+            kept.push(MinMax(range.1 + 1, last.1));
+        }
+        self.ranges.splice(overlapping, kept);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < value);
+        self.ranges.get(i).is_some_and(|r| r.contains(value))
+    }
+
+    // Whether every integer in `range` is in the set.
+    pub fn covers(&self, range: MinMax) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < range.0);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.0 <= range.0 && range.1 <= r.1)
+    }
+
+    // The parts of the set that fall inside `range`, in order.
+    pub fn query(&self, range: MinMax) -> impl Iterator<Item = MinMax> + '_ {
+        self.ranges[self.touching(range, 0)]
+            .iter()
+            .filter_map(move |r| r.intersect(range))
+    }
+}
+
+impl FromIterator<MinMax> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = MinMax>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+// Prints the ranges as a union, e.g. `[0, 3] ∪ [5, 9]`, or `∅` when empty.
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ranges.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "[{}]", range)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,22 @@
 use std::fmt;
 
+// Activity: the `Complex` type and its arithmetic live in their own module,
+// as do `MinMax` and `Point2D`, which grew into an interval and a vector type.
+mod complex;
+mod fft;
+mod fractal;
+mod interval;
+mod point;
+mod polygon;
+mod polynomial;
+
+use complex::Complex;
+use fractal::{Fractal, Renderer};
+use interval::{IntervalSet, MinMax};
+use point::{Orientation, Point2D};
+use polygon::Polygon;
+use polynomial::Polynomial;
+
 // Define a structure for which `fmt::Display` will be implemented. This is
 // a tuple struct named `Structure` that contains an `i32`.
 struct Structure(i32);
@@ -17,64 +34,51 @@ impl fmt::Display for Structure {
     }
 }
 
-// A structure holding two numbers. `Debug` will be derived so the results can
-// be contrasted with `Display`.
-#[derive(Debug)]
-struct MinMax(i64, i64);
-
-// Implement `Display` for `MinMax`.
-impl fmt::Display for MinMax {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Use `self.number` to refer to each positional data point.
-        write!(f, "{}, {}", self.0, self.1)
-    }
-}
-
-// Define a structure where the fields are nameable for comparison.
-#[derive(Debug)]
-struct Point2D {
-    x: f64,
-    y: f64,
-}
-
-// Similarly, implement `Display` for `Point2D`
-impl fmt::Display for Point2D {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Customize so only `x` and `y` are denoted.
-        write!(f, "x: {}, y: {}", self.x, self.y)
-    }
-}
-
-// Activity
-#[derive(Debug)]
-struct Complex {
-    real: f64,
-    imag: f64,
-}
-
-impl fmt::Display for Complex {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} + {}i", self.real, self.imag)
-    }
-}
-
 fn main() {
     // Testing custom display implementation on Structure tuple struct
     println!("{}", Structure(1));
 
-    let minmax = MinMax(0, 14);
+    let minmax = MinMax::new(0, 14).unwrap();
 
     println!("Compare structures:");
     println!("Display: {}", minmax);
     println!("Debug: {:?}", minmax);
 
-    let big_range = MinMax(-300, 300);
-    let small_range = MinMax(-3, 3);
+    let big_range = MinMax::new(-300, 300).unwrap();
+    let small_range = MinMax::new(-3, 3).unwrap();
 
     println!("The big range is {big} and the small is {small}",
              small = small_range,
              big = big_range);
 
+    // `MinMax` is a closed interval, and the bounds must be in order.
+    assert!(MinMax::new(5, 1).is_err());
+    assert_eq!(MinMax::from_iter([4, -2, 9, 0]), MinMax::new(-2, 9).ok());
+    assert_eq!(MinMax::from_iter(Vec::new()), None);
+    assert!(small_range.contains(-3) && !small_range.contains(4));
+    assert_eq!(big_range.len(), 601);
+    assert_eq!(big_range.intersect(minmax), Some(minmax));
+    assert_eq!(small_range.hull(minmax), MinMax::new(-3, 14).unwrap());
+    assert!(small_range.overlaps(minmax));
+    assert_eq!(minmax.clamp(-7), 0);
+    let (low, high) = minmax.split_at(10).unwrap();
+    println!("Splitting {} at 10 gives {} and {}", minmax, low, high);
+
+    // An `IntervalSet` merges whatever overlaps or touches.
+    let mut set: IntervalSet = [(1, 3), (8, 10), (4, 5), (20, 30)]
+        .into_iter()
+        .map(|(min, max)| MinMax::new(min, max).unwrap())
+        .collect();
+    assert_eq!(set.to_string(), "[1, 5] ∪ [8, 10] ∪ [20, 30]");
+    set.remove(MinMax::new(24, 26).unwrap());
+    set.insert(MinMax::new(6, 6).unwrap());
+    assert_eq!(set.to_string(), "[1, 6] ∪ [8, 10] ∪ [20, 23] ∪ [27, 30]");
+    assert!(set.contains(9) && !set.contains(7));
+    assert!(set.covers(MinMax::new(2, 6).unwrap()));
+    let window = MinMax::new(5, 21).unwrap();
+    let hits: Vec<String> = set.query(window).map(|r| format!("[{}]", r)).collect();
+    println!("{} within [{}] is {}", set, window, hits.join(" ∪ "));
+
     let point = Point2D { x: 3.3, y: 7.2 };
 
     println!("Compare points:");
@@ -85,7 +89,206 @@ fn main() {
     // requires `fmt::Binary` to be implemented. This will not work.
     // println!("What does Point2D look like in binary: {:b}?", point);
 
+    // Points are also 2D vectors.
+    let other = Point2D::new(-1.0, 2.0);
+    assert_eq!(point + other, Point2D::new(2.3, 9.2));
+    assert_eq!(2.0 * other - other / 2.0, Point2D::new(-1.5, 3.0));
+    assert_eq!(Point2D::new(3.0, 4.0).length(), 5.0);
+    assert_eq!(Point2D::ORIGIN.distance(Point2D::new(3.0, 4.0)), 5.0);
+    assert_eq!(Point2D::ORIGIN.normalize(), None);
+    assert!((point.normalize().unwrap().length() - 1.0).abs() < 1e-12);
+    assert_eq!(point.lerp(other, 0.5), Point2D::new(1.15, 4.6));
+
+    let unit_x = Point2D::new(1.0, 0.0);
+    let unit_y = Point2D::new(0.0, 1.0);
+    assert_eq!(unit_x.dot(unit_y), 0.0);
+    assert_eq!(unit_x.cross(unit_y), 1.0);
+    assert_eq!(unit_x.angle_between(unit_y), std::f64::consts::FRAC_PI_2);
+    let quarter_turn = std::f64::consts::FRAC_PI_2;
+    assert!(unit_x.rotate(quarter_turn, Point2D::ORIGIN).approx_eq(unit_y, 1e-12));
+    assert!(Point2D::new(2.0, 0.0)
+        .rotate(quarter_turn, unit_x)
+        .approx_eq(Point2D::new(1.0, 1.0), 1e-12));
+
+    // The orientation predicate stays exact where the naive determinant
+    // rounds to zero or to the wrong sign.
+    assert_eq!(
+        Point2D::orientation(Point2D::ORIGIN, unit_x, unit_y),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(
+        Point2D::orientation(Point2D::ORIGIN, unit_y, unit_x),
+        Orientation::Clockwise
+    );
+    let a = Point2D::new(0.5, 0.5);
+    let b = Point2D::new(12.0, 12.0);
+    let c = Point2D::new(24.0, 24.0);
+    assert_eq!(Point2D::orientation(a, b, c), Orientation::Collinear);
+    let nudged = Point2D::new(0.5 + f64::EPSILON, 0.5);
+    assert_eq!(Point2D::orientation(nudged, b, c), Orientation::Clockwise);
+    println!("{} -> {} -> {} is {:?}", nudged, b, c, Point2D::orientation(nudged, b, c));
+
+    // Polygons are built from `Point2D` and read and write WKT.
+    let triangle: Polygon = "POLYGON((0 0, 4 0, 4 3, 0 0))".parse().unwrap();
+    assert_eq!(triangle.signed_area(), 6.0);
+    assert_eq!(triangle.perimeter(), 12.0);
+    assert!(triangle.centroid().unwrap().approx_eq(Point2D::new(8.0 / 3.0, 1.0), 1e-12));
+    assert!(triangle.contains(Point2D::new(3.0, 1.0)));
+    assert!(triangle.contains(Point2D::new(2.0, 0.0)));
+    assert!(!triangle.contains(Point2D::new(1.0, 2.0)));
+    assert!(triangle.is_simple() && triangle.is_convex());
+    assert_eq!(triangle.to_string().parse(), Ok(triangle.clone()));
+    println!("{} has area {}", triangle, triangle.area());
+
+    let bowtie = Polygon(vec![
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(0.0, 2.0),
+    ]);
+    assert!(!bowtie.is_simple());
+
+    let scattered = Polygon(vec![
+        Point2D::new(1.0, 1.0),
+        Point2D::new(0.0, 0.0),
+        Point2D::new(2.0, 0.0),
+        Point2D::new(1.0, 0.0),
+        Point2D::new(2.0, 2.0),
+        Point2D::new(0.5, 1.5),
+        Point2D::new(0.0, 2.0),
+    ]);
+    let hull = scattered.convex_hull();
+    assert_eq!(hull.to_string(), "POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))");
+    println!("The convex hull of {} is {}", scattered, hull);
+
+    for bad in ["POLYGON((0 0, 1 0, 0 0))", "POLYGON((0 0, 1 0, 1 1, 0 1))", "POINT(1 2)"] {
+        if let Err(e) = bad.parse::<Polygon>() {
+            println!("{:?}: {}", bad, e);
+        }
+    }
+
     let complex = Complex { real: 3.0, imag: 10.2 };
     println!("Display: {}", complex);
-    println!("Debug: {:?}", complex)
+    println!("Debug: {:?}", complex);
+
+    // Arithmetic mixes freely with `f64` operands.
+    let z = Complex::new(1.0, 2.0);
+    let w = Complex::new(3.0, -1.0);
+    assert_eq!(z + w, Complex::new(4.0, 1.0));
+    assert_eq!(z - w, Complex::new(-2.0, 3.0));
+    assert_eq!(z * w, Complex::new(5.0, 5.0));
+    assert!((z * w / w).approx_eq(z, 1e-12));
+    assert_eq!(2.0 * z + 1.0, Complex::new(3.0, 4.0));
+    assert_eq!(-z, Complex::new(-1.0, -2.0));
+    assert_eq!(z * z.conj(), Complex::from(z.norm_sqr()));
+    println!("({}) * ({}) = {}", z, w, z * w);
+
+    // Polar form round-trips through `norm` and `arg`.
+    let (r, theta) = z.to_polar();
+    assert!(Complex::from_polar(r, theta).approx_eq(z, 1e-12));
+
+    // Transcendental functions take the principal branch, with the cut along
+    // the negative real axis.
+    let pi_i = Complex::new(0.0, std::f64::consts::PI);
+    assert!(pi_i.exp().approx_eq(-Complex::ONE, 1e-12));
+    assert!(z.ln().exp().approx_eq(z, 1e-12));
+    assert_eq!(Complex::new(-4.0, 0.0).sqrt(), Complex::new(0.0, 2.0));
+    assert_eq!(Complex::new(-4.0, -0.0).sqrt(), Complex::new(0.0, -2.0));
+    assert!(z.sqrt().powf(2.0).approx_eq(z, 1e-12));
+    let i_to_the_i = Complex::from((-std::f64::consts::FRAC_PI_2).exp());
+    assert!(Complex::I.powc(Complex::I).approx_eq(i_to_the_i, 1e-12));
+    assert_eq!(Complex::I.powi(2), -Complex::ONE);
+    println!("sqrt({}) = {}", z, z.sqrt());
+    println!("i^i = {}", Complex::I.powc(Complex::I));
+
+    // `Display` picks the operator from the sign of the imaginary part and
+    // honours precision; `{:#}` prints the polar form.
+    assert_eq!(format!("{}", Complex::new(3.0, -2.0)), "3 - 2i");
+    assert_eq!(format!("{:.3}", z.sqrt()), "1.272 + 0.786i");
+    println!("{} in polar form is {:#.4}", z, z);
+    let polar: Complex = format!("{:#}", z).parse().unwrap();
+    assert!(polar.approx_eq(z, 1e-12));
+
+    // Several notations parse, and whatever `Display` writes reads back.
+    assert_eq!("3-2i".parse(), Ok(Complex::new(3.0, -2.0)));
+    assert_eq!("-i".parse(), Ok(Complex::new(0.0, -1.0)));
+    assert_eq!("4".parse(), Ok(Complex::new(4.0, 0.0)));
+    assert_eq!("2.5e3+1e-2i".parse(), Ok(Complex::new(2500.0, 0.01)));
+    assert_eq!("(1,2)".parse(), Ok(Complex::new(1.0, 2.0)));
+    for c in [
+        complex,
+        z.sqrt(),
+        Complex::new(-0.1, -1e-300),
+        Complex::new(f64::INFINITY, f64::NEG_INFINITY),
+    ] {
+        assert_eq!(format!("{}", c).parse(), Ok(c));
+    }
+
+    // Errors report what went wrong and where.
+    for bad in ["", "3 + 2", "1.2.3i", "(1, 2"] {
+        match bad.parse::<Complex>() {
+            Ok(c) => println!("{:?} unexpectedly parsed as {}", bad, c),
+            Err(e) => println!("{:?}: {}", bad, e),
+        }
+    }
+
+    // Polynomials over `Complex`: `3x^2 - 2x + 1` is written lowest power
+    // first.
+    let poly = Polynomial::from_real(&[1.0, -2.0, 3.0]);
+    assert_eq!(format!("{}", poly), "3x^2 - 2x + 1");
+    assert_eq!(format!("{}", poly.derivative()), "6x - 2");
+    assert_eq!(poly.eval(Complex::from(2.0)), Complex::from(9.0));
+    for root in poly.roots().unwrap() {
+        assert!(poly.eval(root).approx_eq(Complex::ZERO, 1e-12));
+        println!("{} has a root at {:.6}", poly, root);
+    }
+
+    // Rebuilding a polynomial from known roots and solving it again gives
+    // those roots back.
+    let expected = [
+        Complex::new(1.0, 0.0),
+        Complex::new(-2.0, 0.5),
+        Complex::new(-2.0, -0.5),
+        Complex::new(0.0, 3.0),
+    ];
+    let char_poly = Polynomial::from_roots(&expected);
+    println!("Characteristic polynomial: {:.2}", char_poly);
+    let found = char_poly.roots().unwrap();
+    for root in expected {
+        assert!(found.iter().any(|r| r.approx_eq(root, 1e-9)));
+    }
+
+    // Convolution multiplies polynomials: (1 + 2x)(3 + 4x + 5x^2).
+    let product = fft::convolve_real(&[1.0, 2.0], &[3.0, 4.0, 5.0]);
+    let rounded: Vec<f64> = product.iter().map(|x| x.round()).collect();
+    assert_eq!(rounded, [3.0, 10.0, 13.0, 10.0]);
+
+    // A real cosine puts all its energy in a single bin of the half spectrum,
+    // and `irfft` brings the samples back.
+    let samples: Vec<f64> = (0..10)
+        .map(|k| (2.0 * std::f64::consts::PI * 3.0 * k as f64 / 10.0).cos())
+        .collect();
+    let half = fft::rfft(&samples);
+    assert_eq!(half.len(), 6);
+    assert!(half[3].approx_eq(Complex::from(5.0), 1e-9));
+    for (a, b) in fft::irfft(&half, samples.len()).iter().zip(&samples) {
+        assert!((a - b).abs() < 1e-12);
+    }
+
+    // Render the Mandelbrot set and a Julia set to binary PPM files.
+    let renderer = Renderer::new(480, 320).max_iterations(500);
+    let inside = renderer.escape_time(Complex::new(-0.1, 0.1));
+    let outside = renderer.escape_time(Complex::new(1.0, 1.0));
+    assert_eq!(inside, None);
+    assert!(outside.is_some_and(|mu| mu < 3.0));
+
+    let julia = Renderer::new(480, 320).fractal(Fractal::Julia(Complex::new(-0.8, 0.156)));
+    for (name, renderer) in [("mandelbrot", renderer), ("julia", julia)] {
+        let path = std::env::temp_dir().join(format!("{}.ppm", name));
+        let image = renderer.render();
+        match image.save_ppm(&path) {
+            Ok(()) => println!("Wrote {}x{} image to {}", image.width, image.height, path.display()),
+            Err(e) => println!("Could not write {}: {}", path.display(), e),
+        }
+    }
 }
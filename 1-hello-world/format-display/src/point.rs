@@ -0,0 +1,240 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+// Define a structure where the fields are nameable for comparison. Besides
+// being printable it doubles as a 2D vector, so points can be added,
+// subtracted and scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+// Similarly, implement `Display` for `Point2D`
+impl fmt::Display for Point2D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Customize so only `x` and `y` are denoted.
+        write!(f, "x: {}, y: {}", self.x, self.y)
+    }
+}
+
+// Which way the path `a → b → c` turns, with the y axis pointing up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+impl Point2D {
+    pub const ORIGIN: Point2D = Point2D { x: 0.0, y: 0.0 };
+
+    pub const fn new(x: f64, y: f64) -> Point2D {
+        Point2D { x, y }
+    }
+
+    pub fn dot(self, other: Point2D) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    // The z component of the 3D cross product: positive when `other` lies
+    // counter-clockwise of `self`.
+    pub fn cross(self, other: Point2D) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    pub fn length_sqr(self) -> f64 {
+        self.dot(self)
+    }
+
+    // The unit vector in the same direction, or `None` for the zero vector.
+    pub fn normalize(self) -> Option<Point2D> {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            None
+        } else {
+            Some(self / length)
+        }
+    }
+
+    pub fn distance(self, other: Point2D) -> f64 {
+        (other - self).length()
+    }
+
+    // Linear interpolation: `t = 0` gives `self`, `t = 1` gives `other`.
+    pub fn lerp(self, other: Point2D, t: f64) -> Point2D {
+        self + (other - self) * t
+    }
+
+    // Rotate counter-clockwise by `angle` radians around `about`.
+    pub fn rotate(self, angle: f64, about: Point2D) -> Point2D {
+        let (sin, cos) = angle.sin_cos();
+        let d = self - about;
+        about + Point2D::new(d.x * cos - d.y * sin, d.x * sin + d.y * cos)
+    }
+
+    // The signed angle in `(-π, π]` that rotates the direction of `self` onto
+    // the direction of `other`, counter-clockwise positive.
+    pub fn angle_between(self, other: Point2D) -> f64 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    // Whether both coordinates are within `epsilon` of each other. The
+    // tolerance is relative for large coordinates and absolute near zero.
+    pub fn approx_eq(self, other: Point2D, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= epsilon * a.abs().max(b.abs()).max(1.0);
+        close(self.x, other.x) && close(self.y, other.y)
+    }
+
+    // Which way `a → b → c` turns. The sign of the determinant is computed
+    // exactly, so nearly collinear points are never misclassified by
+    // rounding error.
+    pub fn orientation(a: Point2D, b: Point2D, c: Point2D) -> Orientation {
+        let det = orient2d(a, b, c);
+        if det > 0.0 {
+            Orientation::CounterClockwise
+        } else if det < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+}
+
+// A value with the same sign as `(b - a) × (c - a)`, after Shewchuk's
+// adaptive predicate: the plain floating-point determinant is used whenever
+// it is provably far enough from zero, and only otherwise is it recomputed
+// with exact expansion arithmetic.
+fn orient2d(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+
+    // Shewchuk's `ccwerrboundA`.
+    const EPS: f64 = f64::EPSILON / 2.0;
+    let bound = (3.0 + 16.0 * EPS) * EPS * (left.abs() + right.abs());
+    if det.abs() > bound {
+        return det;
+    }
+
+    // Multiplying out the determinant gives six products whose exact sum has
+    // the sign we need; each product is split into a head and a rounding tail.
+    let products = [
+        (a.x, b.y),
+        (-a.x, c.y),
+        (-c.x, b.y),
+        (-a.y, b.x),
+        (a.y, c.x),
+        (c.y, b.x),
+    ];
+    let mut expansion: Vec<f64> = Vec::with_capacity(12);
+    for (p, q) in products {
+        let (hi, lo) = two_product(p, q);
+        grow_expansion(&mut expansion, lo);
+        grow_expansion(&mut expansion, hi);
+    }
+
+    // Components are kept in increasing magnitude and do not overlap, so the
+    // largest one carries the sign of the sum.
+    expansion.last().copied().unwrap_or(0.0)
+}
+
+// `a + b` as an unevaluated pair `(sum, error)` with `sum + error` exact.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+// `a · b` as an unevaluated pair `(product, error)`, using a fused
+// multiply-add to recover the rounding error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+// Add `b` into a non-overlapping expansion, dropping zero components.
+fn grow_expansion(expansion: &mut Vec<f64>, b: f64) {
+    let mut carry = b;
+    let mut out = Vec::with_capacity(expansion.len() + 1);
+    for &e in expansion.iter() {
+        let (sum, err) = two_sum(carry, e);
+        if err != 0.0 {
+            out.push(err);
+        }
+        carry = sum;
+    }
+    if carry != 0.0 {
+        out.push(carry);
+    }
+    *expansion = out;
+}
+
+impl Neg for Point2D {
+    type Output = Point2D;
+
+    fn neg(self) -> Point2D {
+        Point2D::new(-self.x, -self.y)
+    }
+}
+
+impl Add for Point2D {
+    type Output = Point2D;
+
+    fn add(self, rhs: Point2D) -> Point2D {
+        Point2D::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point2D {
+    type Output = Point2D;
+
+    fn sub(self, rhs: Point2D) -> Point2D {
+        Point2D::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f64> for Point2D {
+    type Output = Point2D;
+
+    fn mul(self, rhs: f64) -> Point2D {
+        Point2D::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Mul<Point2D> for f64 {
+    type Output = Point2D;
+
+    fn mul(self, rhs: Point2D) -> Point2D {
+        rhs * self
+    }
+}
+
+impl Div<f64> for Point2D {
+    type Output = Point2D;
+
+    fn div(self, rhs: f64) -> Point2D {
+        Point2D::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl AddAssign for Point2D {
+    fn add_assign(&mut self, rhs: Point2D) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point2D {
+    fn sub_assign(&mut self, rhs: Point2D) {
+        *self = *self - rhs;
+    }
+}
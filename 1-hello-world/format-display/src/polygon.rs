@@ -0,0 +1,412 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::point::{Orientation, Point2D};
+
+// A polygon given by its vertices in order. The ring is implicitly closed:
+// the last vertex connects back to the first and is not repeated.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon(pub Vec<Point2D>);
+
+impl Polygon {
+    // The edges `(v[i], v[i + 1])`, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = (Point2D, Point2D)> + '_ {
+        let n = self.0.len();
+        (0..n).map(move |i| (self.0[i], self.0[(i + 1) % n]))
+    }
+
+    // The shoelace formula: positive when the vertices run counter-clockwise,
+    // negative when clockwise.
+    pub fn signed_area(&self) -> f64 {
+        self.edges().map(|(a, b)| a.cross(b)).sum::<f64>() / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    // The centre of mass of the enclosed region, or `None` when the polygon
+    // encloses no area.
+    pub fn centroid(&self) -> Option<Point2D> {
+        let area = self.signed_area();
+        if area == 0.0 || !area.is_finite() {
+            return None;
+        }
+
+        let sum = self
+            .edges()
+            .fold(Point2D::ORIGIN, |acc, (a, b)| acc + (a + b) * a.cross(b));
+        Some(sum / (6.0 * area))
+    }
+
+    // Whether `p` lies inside the polygon or on its boundary, using the
+    // winding number so that self-overlapping polygons behave sensibly.
+    pub fn contains(&self, p: Point2D) -> bool {
+        let mut winding = 0;
+
+        for (a, b) in self.edges() {
+            if on_segment(a, b, p) {
+                return true;
+            }
+            if a.y <= p.y {
+                if b.y > p.y && Point2D::orientation(a, b, p) == Orientation::CounterClockwise {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && Point2D::orientation(a, b, p) == Orientation::Clockwise {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+
+    // Whether the boundary never touches itself: at least three vertices,
+    // no repeated vertices, and no two edges meeting anywhere other than at
+    // their shared corner.
+    pub fn is_simple(&self) -> bool {
+        let n = self.0.len();
+        if n < 3 {
+            return false;
+        }
+
+        let edges: Vec<_> = self.edges().collect();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b) = edges[i];
+                let (c, d) = edges[j];
+                let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+
+                if adjacent {
+                    // Neighbours share exactly one vertex; they must not fold
+                    // back along each other.
+                    let (shared, far_i, far_j) = if j == i + 1 { (b, a, d) } else { (a, b, c) };
+                    if far_i == shared
+                        || far_j == shared
+                        || on_segment(shared, far_i, far_j)
+                        || on_segment(shared, far_j, far_i)
+                    {
+                        return false;
+                    }
+                } else if segments_intersect(a, b, c, d) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn is_convex(&self) -> bool {
+        let n = self.0.len();
+        if !self.is_simple() {
+            return false;
+        }
+
+        let mut turn = None;
+        for i in 0..n {
+            let o = Point2D::orientation(self.0[i], self.0[(i + 1) % n], self.0[(i + 2) % n]);
+            if o == Orientation::Collinear {
+                continue;
+            }
+            if *turn.get_or_insert(o) != o {
+                return false;
+            }
+        }
+        true
+    }
+
+    // The smallest convex polygon containing every vertex, found with
+    // Andrew's monotone chain. The hull runs counter-clockwise from the
+    // leftmost point and leaves out collinear points along its edges.
+    pub fn convex_hull(&self) -> Polygon {
+        let mut points = self.0.clone();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+
+        if points.len() < 3 {
+            return Polygon(points);
+        }
+
+        // Build the lower hull left to right and the upper hull right to
+        // left, popping any point that does not make a left turn.
+        let half_hull = |points: &mut dyn Iterator<Item = Point2D>| {
+            let mut hull: Vec<Point2D> = Vec::new();
+            for p in points {
+                while hull.len() >= 2
+                    && Point2D::orientation(hull[hull.len() - 2], hull[hull.len() - 1], p)
+                        != Orientation::CounterClockwise
+                {
+                    hull.pop();
+                }
+                hull.push(p);
+            }
+            // Each half ends where the other begins.
+            hull.pop();
+            hull
+        };
+
+        let mut hull = half_hull(&mut points.iter().copied());
+        hull.extend(half_hull(&mut points.iter().rev().copied()));
+        Polygon(hull)
+    }
+}
+
+// Whether `p` lies on the closed segment `a`–`b`.
+fn on_segment(a: Point2D, b: Point2D, p: Point2D) -> bool {
+    Point2D::orientation(a, b, p) == Orientation::Collinear
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+// Whether the closed segments `a`–`b` and `c`–`d` share at least one point.
+fn segments_intersect(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> bool {
+    let o1 = Point2D::orientation(a, b, c);
+    let o2 = Point2D::orientation(a, b, d);
+    let o3 = Point2D::orientation(c, d, a);
+    let o4 = Point2D::orientation(c, d, b);
+
+    let proper = o1 != o2
+        && o3 != o4
+        && ![o1, o2, o3, o4].contains(&Orientation::Collinear);
+
+    proper
+        || on_segment(a, b, c)
+        || on_segment(a, b, d)
+        || on_segment(c, d, a)
+        || on_segment(c, d, b)
+}
+
+// Writes Well-Known Text, closing the ring by repeating the first vertex:
+// `POLYGON((0 0, 4 0, 4 3, 0 0))`. An empty polygon is `POLYGON EMPTY`.
+impl fmt::Display for Polygon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.0.first() else {
+            return write!(f, "POLYGON EMPTY");
+        };
+
+        write!(f, "POLYGON((")?;
+        for (i, p) in self.0.iter().chain([first]).enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", p.x, p.y)?;
+        }
+        write!(f, "))")
+    }
+}
+
+// What went wrong while reading a WKT polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePolygonErrorKind {
+    // The text does not start with the `POLYGON` keyword.
+    ExpectedKeyword,
+    // A character that does not fit the grammar at this point.
+    UnexpectedChar(char),
+    // The input stopped before the polygon was complete.
+    UnexpectedEnd,
+    // A coordinate could not be read as a number.
+    InvalidNumber,
+    // The last position of the ring differs from the first.
+    UnclosedRing,
+    // A ring needs at least four positions, the first and last being equal.
+    TooFewPoints,
+    // Polygons with holes cannot be represented by `Polygon`.
+    InteriorRing,
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePolygonError {
+    kind: ParsePolygonErrorKind,
+    position: usize,
+}
+
+impl ParsePolygonError {
+    pub fn kind(&self) -> ParsePolygonErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParsePolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParsePolygonErrorKind::ExpectedKeyword => write!(f, "expected `POLYGON`")?,
+            ParsePolygonErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c)?,
+            ParsePolygonErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParsePolygonErrorKind::InvalidNumber => write!(f, "invalid coordinate")?,
+            ParsePolygonErrorKind::UnclosedRing => {
+                write!(f, "ring does not end where it starts")?
+            }
+            ParsePolygonErrorKind::TooFewPoints => {
+                write!(f, "ring needs at least four positions")?
+            }
+            ParsePolygonErrorKind::InteriorRing => {
+                write!(f, "polygons with interior rings are not supported")?
+            }
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParsePolygonError {}
+
+// Reads a single-ring WKT polygon such as `POLYGON((0 0, 4 0, 4 3, 0 0))`.
+// The keyword is case-insensitive and whitespace is free between tokens.
+impl FromStr for Polygon {
+    type Err = ParsePolygonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { src: s, pos: 0 };
+        parser.skip_whitespace();
+        if !parser.keyword("POLYGON") {
+            return Err(parser.error(ParsePolygonErrorKind::ExpectedKeyword));
+        }
+        parser.skip_whitespace();
+
+        let polygon = if parser.keyword("EMPTY") {
+            Polygon::default()
+        } else {
+            parser.expect('(')?;
+            let ring = parser.ring()?;
+            parser.skip_whitespace();
+            if parser.peek() == Some(',') {
+                return Err(parser.error(ParsePolygonErrorKind::InteriorRing));
+            }
+            parser.expect(')')?;
+            ring
+        };
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(polygon),
+            Some(_) => Err(parser.unexpected()),
+        }
+    }
+}
+
+// A cursor over the input; positions are byte offsets.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, kind: ParsePolygonErrorKind) -> ParsePolygonError {
+        ParsePolygonError {
+            kind,
+            position: self.pos,
+        }
+    }
+
+    fn unexpected(&self) -> ParsePolygonError {
+        match self.peek() {
+            Some(c) => self.error(ParsePolygonErrorKind::UnexpectedChar(c)),
+            None => self.error(ParsePolygonErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        let matches = self
+            .rest()
+            .get(..word.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(word));
+        if matches {
+            self.pos += word.len();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParsePolygonError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    // `(x y, x y, ...)` with the closing position equal to the opening one.
+    fn ring(&mut self) -> Result<Polygon, ParsePolygonError> {
+        self.expect('(')?;
+
+        let mut points = vec![self.position()?];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    points.push(self.position()?);
+                }
+                Some(')') => break,
+                _ => return Err(self.unexpected()),
+            }
+        }
+
+        if points.len() < 4 {
+            return Err(self.error(ParsePolygonErrorKind::TooFewPoints));
+        }
+        if points.first() != points.last() {
+            return Err(self.error(ParsePolygonErrorKind::UnclosedRing));
+        }
+        self.pos += 1;
+
+        points.pop();
+        Ok(Polygon(points))
+    }
+
+    // A position is two whitespace-separated coordinates.
+    fn position(&mut self) -> Result<Point2D, ParsePolygonError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(Point2D::new(x, y))
+    }
+
+    fn number(&mut self) -> Result<f64, ParsePolygonError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let rest = self.rest();
+        let len = rest.len()
+            - rest
+                .trim_start_matches(|c: char| c.is_ascii_digit() || "+-.eE".contains(c))
+                .len();
+        if len == 0 {
+            return Err(self.unexpected());
+        }
+
+        self.pos += len;
+        self.src[start..self.pos]
+            .parse()
+            .map_err(|_| ParsePolygonError {
+                kind: ParsePolygonErrorKind::InvalidNumber,
+                position: start,
+            })
+    }
+}
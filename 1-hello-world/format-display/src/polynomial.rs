@@ -0,0 +1,322 @@
+// `allow` required to silence warnings because `main` only exercises part of
+// the API.
+#![allow(dead_code)]
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::complex::Complex;
+
+// A polynomial with complex coefficients, stored lowest power first so that
+// `coeffs[i]` multiplies `x^i`. Trailing zero coefficients are trimmed, which
+// keeps the degree honest and makes the zero polynomial an empty vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    coeffs: Vec<Complex>,
+}
+
+impl Polynomial {
+    pub fn new(coeffs: Vec<Complex>) -> Polynomial {
+        let mut coeffs = coeffs;
+        while coeffs.last() == Some(&Complex::ZERO) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    // Convenience constructor for real coefficients, lowest power first.
+    pub fn from_real(coeffs: &[f64]) -> Polynomial {
+        Polynomial::new(coeffs.iter().map(|&c| Complex::from(c)).collect())
+    }
+
+    // The monic polynomial `(x - r0)(x - r1)...` with the given roots.
+    pub fn from_roots(roots: &[Complex]) -> Polynomial {
+        let mut coeffs = vec![Complex::ONE];
+        for &root in roots {
+            // Multiply by `(x - root)`: shift everything up one power and
+            // subtract `root` times the old coefficients.
+            coeffs.insert(0, Complex::ZERO);
+            for i in 0..coeffs.len() - 1 {
+                let next = coeffs[i + 1];
+                coeffs[i] -= root * next;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+
+    pub fn coeffs(&self) -> &[Complex] {
+        &self.coeffs
+    }
+
+    // The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    // Evaluate at `x` with Horner's method, one multiply-add per coefficient.
+    pub fn eval(&self, x: Complex) -> Complex {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(Complex::ZERO, |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(power, &c)| c * power as f64)
+                .collect(),
+        )
+    }
+
+    // All complex roots, repeated according to multiplicity, using the
+    // default `RootFinder`.
+    pub fn roots(&self) -> Result<Vec<Complex>, RootError> {
+        RootFinder::default().roots(self)
+    }
+}
+
+// Settings for the Aberth–Ehrlich iteration used to find roots. Iteration
+// stops once every correction is within `tolerance` relative to the size of
+// the root it was applied to, or once a root's residual is no bigger than the
+// rounding error of evaluating the polynomial there. The second test is what
+// ends the search near a repeated root, where convergence is only linear and
+// the corrections never get that small.
+#[derive(Debug, Clone, Copy)]
+pub struct RootFinder {
+    pub tolerance: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for RootFinder {
+    fn default() -> Self {
+        RootFinder {
+            tolerance: 1e-12,
+            max_iterations: 500,
+        }
+    }
+}
+
+impl RootFinder {
+    pub fn tolerance(self, tolerance: f64) -> RootFinder {
+        RootFinder { tolerance, ..self }
+    }
+
+    pub fn max_iterations(self, max_iterations: usize) -> RootFinder {
+        RootFinder {
+            max_iterations,
+            ..self
+        }
+    }
+
+    pub fn roots(&self, poly: &Polynomial) -> Result<Vec<Complex>, RootError> {
+        let Some(degree) = poly.degree() else {
+            return Err(RootError::ZeroPolynomial);
+        };
+
+        // Roots at the origin are exact; factor them out so the iteration
+        // only has to deal with the rest.
+        let zeros = poly.coeffs.iter().take_while(|&&c| c == Complex::ZERO).count();
+        let mut roots = vec![Complex::ZERO; zeros];
+
+        // Dividing through by the leading coefficient makes it monic.
+        let lead = poly.coeffs[degree];
+        let monic = Polynomial::new(poly.coeffs[zeros..].iter().map(|&c| c / lead).collect());
+
+        match monic.degree() {
+            Some(0) => {}
+            Some(1) => roots.push(-monic.coeffs[0]),
+            _ => roots.extend(self.aberth(&monic)?),
+        }
+        Ok(roots)
+    }
+
+    // Refine all roots of a monic polynomial of degree two or more at once.
+    fn aberth(&self, monic: &Polynomial) -> Result<Vec<Complex>, RootError> {
+        let n = monic.coeffs.len() - 1;
+        let derivative = monic.derivative();
+
+        // Start evenly spaced on a circle that encloses every root (the Cauchy
+        // bound), rotated off the axes so no guess sits on a symmetry line.
+        let radius = 1.0
+            + monic.coeffs[..n]
+                .iter()
+                .map(|c| c.norm())
+                .fold(0.0, f64::max);
+        let mut estimates: Vec<Complex> = (0..n)
+            .map(|k| Complex::from_polar(radius, 2.0 * PI * k as f64 / n as f64 + 0.4))
+            .collect();
+
+        // Bounds the error of Horner's method at `z`: `2n` roundings, each
+        // relative to the sum of the terms' magnitudes, with a factor of two
+        // to spare.
+        let rounding = |z: Complex| {
+            let r = z.norm();
+            let magnitude = monic.coeffs.iter().rev().fold(0.0, |acc, c| acc * r + c.norm());
+            4.0 * n as f64 * f64::EPSILON * magnitude
+        };
+
+        for _ in 0..self.max_iterations {
+            let mut converged = true;
+
+            for k in 0..n {
+                let z = estimates[k];
+                let value = monic.eval(z);
+                if value == Complex::ZERO {
+                    continue;
+                }
+                // Once the residual is down to rounding error the steps are
+                // noise and need not shrink any further.
+                let settled = value.norm() <= rounding(z);
+
+                let newton = value / derivative.eval(z);
+                let repulsion: Complex = (0..n)
+                    .filter(|&j| j != k)
+                    .map(|j| (z - estimates[j]).recip())
+                    .fold(Complex::ZERO, |acc, r| acc + r);
+                let step = newton / (1.0 - newton * repulsion);
+
+                // A non-finite step (two estimates collided) is skipped and
+                // counts as not yet converged.
+                if !step.is_finite() {
+                    converged = false;
+                    continue;
+                }
+                estimates[k] = z - step;
+                if !settled && step.norm() > self.tolerance * z.norm().max(1.0) {
+                    converged = false;
+                }
+            }
+
+            if converged {
+                return Ok(estimates);
+            }
+        }
+
+        Err(RootError::NoConvergence { estimates })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RootError {
+    // Every number is a root of the zero polynomial.
+    ZeroPolynomial,
+    // The iteration limit ran out; the last estimates are kept for inspection.
+    NoConvergence { estimates: Vec<Complex> },
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootError::ZeroPolynomial => write!(f, "the zero polynomial has no isolated roots"),
+            RootError::NoConvergence { estimates } => write!(
+                f,
+                "root finding did not converge for a polynomial of degree {}",
+                estimates.len()
+            ),
+        }
+    }
+}
+
+impl Error for RootError {}
+
+// Prints highest power first, e.g. `3x^2 - 2x + 1`. Real coefficients fold
+// their sign into the operator and a coefficient of one is left implicit;
+// coefficients with an imaginary part are parenthesised, as in `(1 + 2i)x`.
+// A precision such as `{:.3}` applies to every coefficient.
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let terms = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| **c != Complex::ZERO);
+
+        for (i, (power, c)) in terms.enumerate() {
+            let first = i == 0;
+
+            if c.imag != 0.0 {
+                if !first {
+                    write!(f, " + ")?;
+                }
+                match f.precision() {
+                    Some(p) => write!(f, "({:.*})", p, c)?,
+                    None => write!(f, "({})", c)?,
+                }
+            } else {
+                let negative = c.real.is_sign_negative();
+                match (first, negative) {
+                    (true, true) => write!(f, "-")?,
+                    (true, false) => {}
+                    (false, true) => write!(f, " - ")?,
+                    (false, false) => write!(f, " + ")?,
+                }
+
+                let magnitude = c.real.abs();
+                if magnitude != 1.0 || power == 0 {
+                    match f.precision() {
+                        Some(p) => write!(f, "{:.*}", p, magnitude)?,
+                        None => write!(f, "{}", magnitude)?,
+                    }
+                }
+            }
+
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", power)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+    use crate::complex::Complex;
+
+    #[test]
+    fn quadruple_root() {
+        let poly = Polynomial::from_roots(&[Complex::from(2.0); 4]);
+        let roots = poly.roots().unwrap();
+        assert_eq!(roots.len(), 4);
+        // A root of multiplicity m is only determined to about ε^(1/m) in
+        // double precision: 1e-4 for four, 6e-6 for three.
+        for root in &roots {
+            assert!((*root - 2.0).norm() < 1e-3, "{}", root);
+        }
+    }
+
+    #[test]
+    fn triple_root() {
+        let poly = Polynomial::from_real(&[-1.0, 3.0, -3.0, 1.0]);
+        let roots = poly.roots().unwrap();
+        assert_eq!(roots.len(), 3);
+        for root in &roots {
+            assert!((*root - 1.0).norm() < 1e-4, "{}", root);
+        }
+    }
+
+    #[test]
+    fn simple_roots_are_accurate() {
+        let expected = [Complex::new(1.0, 2.0), Complex::new(-3.0, 0.5), Complex::from(0.25)];
+        let roots = Polynomial::from_roots(&expected).roots().unwrap();
+        for root in expected {
+            assert!(roots.iter().any(|r| r.approx_eq(root, 1e-12)), "{}", root);
+        }
+    }
+}
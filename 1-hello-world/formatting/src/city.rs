@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::geo::{Coordinates, Distance, NonFiniteCoordinates};
+use crate::solar::{Date, SolarDay};
+
+// The name is usually a string literal, but anything printable will do; an
+// `OwnedCity` holds a `String`, for cities read in at run time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct City<N = &'static str> {
+    pub name: N,
+    // Latitude
+    pub lat: f32,
+    // Longitude
+    pub lon: f32,
+}
+
+pub type OwnedCity = City<String>;
+
+// `{:#}` writes the coordinates in degrees, minutes and seconds instead.
+impl<N: Display> Display for City<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}: {:#}", self.name, self.coordinates());
+        }
+
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+
+        write!(f, "{}: {:.3}°{} {:.3}°{}",
+               self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+    }
+}
+
+// The geodesy is done on `Coordinates` in `f64`; these methods let a `City`
+// be used directly, against another `City` or against bare coordinates.
+impl<N> City<N> {
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates::from(self)
+    }
+
+    // Great-circle distance on a spherical Earth (haversine formula).
+    pub fn distance_to<P: Into<Coordinates>>(&self, other: P) -> Distance {
+        self.coordinates().distance_to(other.into())
+    }
+
+    // Distance on the WGS-84 ellipsoid (Vincenty's inverse formula), or `None`
+    // for nearly antipodal points where the iteration does not converge.
+    pub fn vincenty_distance_to<P: Into<Coordinates>>(&self, other: P) -> Option<Distance> {
+        self.coordinates().vincenty_distance_to(other.into())
+    }
+
+    // The compass bearing in degrees, `[0, 360)`, to set off on.
+    pub fn initial_bearing_to<P: Into<Coordinates>>(&self, other: P) -> f64 {
+        self.coordinates().initial_bearing_to(other.into())
+    }
+
+    // The point halfway along the great circle to `other`.
+    pub fn midpoint<P: Into<Coordinates>>(&self, other: P) -> Coordinates {
+        self.coordinates().midpoint(other.into())
+    }
+
+    // Where you end up after travelling `distance` along the great circle
+    // that starts out on `bearing` degrees.
+    pub fn destination(&self, bearing: f64, distance: Distance) -> Coordinates {
+        self.coordinates().destination(bearing, distance)
+    }
+
+    // Sunrise, sunset, solar noon and civil twilight on `date`, in UTC.
+    pub fn solar_day(&self, date: Date) -> Result<SolarDay, NonFiniteCoordinates> {
+        self.coordinates().solar_day(date)
+    }
+}
+
+impl<N> From<&City<N>> for Coordinates {
+    fn from(city: &City<N>) -> Self {
+        Coordinates {
+            lat: city.lat as f64,
+            lon: city.lon as f64,
+        }
+    }
+}
+
+impl<N> From<City<N>> for Coordinates {
+    fn from(city: City<N>) -> Self {
+        Coordinates::from(&city)
+    }
+}
+
+// Returned by the GeoJSON and KML exporters for the first city, by 0-based
+// index, whose latitude or longitude is NaN or infinite. Neither format has a
+// way to write those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonFiniteCity {
+    pub index: usize,
+}
+
+impl Display for NonFiniteCity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "city {} has a NaN or infinite coordinate", self.index)
+    }
+}
+
+impl Error for NonFiniteCity {}
+
+pub(crate) fn check_finite<N>(cities: &[City<N>]) -> Result<(), NonFiniteCity> {
+    match cities.iter().position(|city| !city.coordinates().is_finite()) {
+        Some(index) => Err(NonFiniteCity { index }),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,773 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::colorspace::{LinearRgb, Oklab};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RGB ({red}, {green}, {blue}) 0x{red:0>2X}{green:0>2X}{blue:0>2X}",
+            red = self.red,
+            green = self.green,
+            blue = self.blue
+        )
+    }
+}
+
+// WCAG 2.x conformance levels for text contrast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WcagLevel {
+    AA,
+    AAA,
+}
+
+// WCAG counts text as large from 18pt, or 14pt bold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSize {
+    Normal,
+    Large,
+}
+
+impl WcagLevel {
+    // The lowest contrast ratio that passes.
+    pub fn min_ratio(self, size: TextSize) -> f64 {
+        match (self, size) {
+            (WcagLevel::AA, TextSize::Normal) => 4.5,
+            (WcagLevel::AA, TextSize::Large) => 3.0,
+            (WcagLevel::AAA, TextSize::Normal) => 7.0,
+            (WcagLevel::AAA, TextSize::Large) => 4.5,
+        }
+    }
+}
+
+// The contrast between a foreground and a background colour, from 1 (none)
+// to 21 (black on white).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Contrast(pub f64);
+
+impl Contrast {
+    pub fn ratio(self) -> f64 {
+        self.0
+    }
+
+    pub fn passes(self, level: WcagLevel, size: TextSize) -> bool {
+        self.0 >= level.min_ratio(size)
+    }
+}
+
+// `4.48:1 (AA large)`: the ratio and the best level it reaches, if any.
+impl Display for Contrast {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:.2}:1", self.0)?;
+        let levels = [
+            (WcagLevel::AAA, TextSize::Normal, "AAA"),
+            (WcagLevel::AA, TextSize::Normal, "AA"),
+            (WcagLevel::AA, TextSize::Large, "AA large"),
+        ];
+        match levels.iter().find(|(level, size, _)| self.passes(*level, *size)) {
+            Some((_, _, name)) => write!(f, " ({})", name),
+            None => write!(f, " (fails)"),
+        }
+    }
+}
+
+impl Color {
+    // How bright the colour looks, from 0 for black to 1 for white, as WCAG
+    // defines it. WCAG 2 quotes 0.03928 as the sRGB curve's threshold where
+    // the sRGB standard has 0.04045; no 8-bit channel falls in between, so
+    // the standard curve is used.
+    pub fn relative_luminance(self) -> f64 {
+        let c = self.to_linear_rgb();
+        0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+    }
+
+    // The WCAG contrast ratio against `other`. It is symmetric, so which is
+    // the foreground does not matter.
+    pub fn contrast(self, other: Color) -> Contrast {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        Contrast((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+
+    // The closest colour to `self`, as CIEDE2000 sees it, that passes against
+    // `background` at the given level, found by raising or lowering only its
+    // OKLab lightness. Where that leaves sRGB, chroma is given up rather than
+    // hue, so the result is a lighter or darker shade of the same hue, and a
+    // grey stays grey. Returns `self` if it already passes, and `None` if
+    // neither white nor black would pass.
+    pub fn accessible_foreground(
+        self,
+        background: Color,
+        level: WcagLevel,
+        size: TextSize,
+    ) -> Option<Color> {
+        let passes = |c: Color| c.contrast(background).passes(level, size);
+        if passes(self) {
+            return Some(self);
+        }
+
+        let start = self.to_oklab();
+        // A grey's `a` and `b` are zero only up to rounding, which could tip
+        // one channel over before the others, so greys are built directly.
+        let grey = self.red == self.green && self.green == self.blue;
+        let at_lightness = |l: f64| {
+            if grey {
+                let y = l.clamp(0.0, 1.0).powi(3);
+                Color::from(LinearRgb { red: y, green: y, blue: y })
+            } else {
+                clip_chroma(Oklab { l, ..start })
+            }
+        };
+        let toward = |end: Color| {
+            if !passes(end) {
+                return None;
+            }
+            let end = end.to_oklab();
+            let at = |t: f64| at_lightness(start.l + (end.l - start.l) * t);
+            // The smallest step that passes, by bisection: `hi` always
+            // passes, `lo` never does.
+            let (mut lo, mut hi) = (0.0, 1.0);
+            for _ in 0..40 {
+                let mid = (lo + hi) / 2.0;
+                if passes(at(mid)) {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            Some(at(hi))
+        };
+
+        let white = Color { red: 255, green: 255, blue: 255 };
+        let black = Color { red: 0, green: 0, blue: 0 };
+        [toward(white), toward(black)]
+            .into_iter()
+            .flatten()
+            .min_by(|a, b| self.delta_e2000(*a).total_cmp(&self.delta_e2000(*b)))
+    }
+}
+
+// The most saturated colour in sRGB with the lightness and hue of `oklab`,
+// found by shrinking its chroma. Clamping each channel instead would shift
+// the hue.
+fn clip_chroma(oklab: Oklab) -> Color {
+    let l = oklab.l.clamp(0.0, 1.0);
+    let scaled = |s: f64| LinearRgb::from(Oklab { l, a: oklab.a * s, b: oklab.b * s });
+    let in_gamut = |c: LinearRgb| {
+        [c.red, c.green, c.blue].iter().all(|&v| (-1e-9..=1.0 + 1e-9).contains(&v))
+    };
+    if in_gamut(scaled(1.0)) {
+        return Color::from(scaled(1.0));
+    }
+    // With no chroma at all the colour is a grey, which is always in gamut.
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..40 {
+        let mid = (lo + hi) / 2.0;
+        if in_gamut(scaled(mid)) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Color::from(scaled(lo))
+}
+
+// A `Color` with an alpha channel, where 0 is transparent and 255 opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgba {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl Rgba {
+    pub fn color(self) -> Color {
+        Color {
+            red: self.red,
+            green: self.green,
+            blue: self.blue,
+        }
+    }
+
+    // The colour seen when this one is painted over an opaque `background`.
+    pub fn over(self, background: Color) -> Color {
+        let mix = |fg: u8, bg: u8| {
+            let a = self.alpha as u32;
+            ((fg as u32 * a + bg as u32 * (255 - a) + 127) / 255) as u8
+        };
+        Color {
+            red: mix(self.red, background.red),
+            green: mix(self.green, background.green),
+            blue: mix(self.blue, background.blue),
+        }
+    }
+}
+
+impl From<Color> for Rgba {
+    fn from(color: Color) -> Self {
+        Rgba {
+            red: color.red,
+            green: color.green,
+            blue: color.blue,
+            alpha: 255,
+        }
+    }
+}
+
+// Like `Color`, with the alpha as a fraction and a fourth hex byte:
+// `RGBA (128, 255, 90, 0.502) 0x80FF5A80`.
+impl Display for Rgba {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RGBA ({red}, {green}, {blue}, {alpha}) 0x{red:0>2X}{green:0>2X}{blue:0>2X}{a:0>2X}",
+            red = self.red,
+            green = self.green,
+            blue = self.blue,
+            alpha = (self.alpha as f64 / 255.0 * 1000.0).round() / 1000.0,
+            a = self.alpha
+        )
+    }
+}
+
+// The notations a colour can be written in, so that an error can say which
+// one the input looked like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorForm {
+    // `#80FF5A`, `#8F5`, `0x80FF5A`, and for `Rgba` also `#80FF5A80` and
+    // `#8F58`.
+    Hex,
+    // `rgb(128, 255, 90)` or `rgb(50%, 100%, 35%)`, and for `Rgba` also
+    // `rgba(128, 255, 90, 0.5)`.
+    Function,
+    // A CSS colour name such as `rebeccapurple`.
+    Name,
+}
+
+impl Display for ColorForm {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ColorForm::Hex => write!(f, "a hex colour like `#80FF5A`, `#8F5` or `0x80FF5A`"),
+            ColorForm::Function => {
+                write!(f, "a function like `rgb(128, 255, 90)` or `rgb(50%, 100%, 35%)`")
+            }
+            ColorForm::Name => write!(f, "a CSS colour name like `seagreen`"),
+        }
+    }
+}
+
+// What went wrong while parsing a `Color` or `Rgba`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseColorErrorKind {
+    // The input was empty or only whitespace.
+    Empty,
+    // A character that is not a hex digit.
+    InvalidHexDigit(char),
+    // A number of hex digits that no hex form has.
+    WrongHexLength(usize),
+    // A character that does not fit the function syntax at this point.
+    UnexpectedChar(char),
+    // The input stopped before the closing parenthesis.
+    UnexpectedEnd,
+    // A function other than `rgb` or `rgba`.
+    UnknownFunction,
+    // Something that started like a number could not be read as one.
+    InvalidNumber,
+    // A channel above 255 or 100%, or an alpha above 1.
+    OutOfRange,
+    // Channels must be all numbers or all percentages.
+    MixedUnits,
+    // The wrong number of arguments to `rgb(...)`.
+    WrongArgumentCount(usize),
+    // An alpha channel, where only `Rgba` can hold one.
+    UnexpectedAlpha,
+    // Not one of the CSS colour names.
+    UnknownName,
+}
+
+// A parse failure: what went wrong, which form the input was taken to be in,
+// and the byte offset where it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError {
+    kind: ParseColorErrorKind,
+    form: Option<ColorForm>,
+    position: usize,
+}
+
+impl ParseColorError {
+    pub fn kind(&self) -> ParseColorErrorKind {
+        self.kind
+    }
+
+    // `None` only for empty input.
+    pub fn form(&self) -> Option<ColorForm> {
+        self.form
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use ParseColorErrorKind::*;
+
+        match self.kind {
+            Empty => return write!(f, "cannot parse a colour from an empty string"),
+            InvalidHexDigit(c) => write!(f, "invalid hex digit `{}`", c)?,
+            WrongHexLength(n) => write!(f, "wrong number of hex digits ({})", n)?,
+            UnexpectedChar(c) => write!(f, "unexpected character `{}`", c)?,
+            UnexpectedEnd => write!(f, "unexpected end of input")?,
+            UnknownFunction => write!(f, "unknown function")?,
+            InvalidNumber => write!(f, "invalid number")?,
+            OutOfRange => write!(f, "value out of range")?,
+            MixedUnits => write!(f, "channels mix numbers and percentages")?,
+            WrongArgumentCount(n) => write!(f, "wrong number of arguments ({})", n)?,
+            UnexpectedAlpha => write!(f, "alpha channel in an opaque colour")?,
+            UnknownName => write!(f, "unknown colour name")?,
+        }
+        write!(f, " at position {}", self.position)?;
+        if let Some(form) = self.form {
+            write!(f, "; expected {}", form)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseColorError {}
+
+// Accepts `#80FF5A`, `#8F5`, `0x80FF5A` (as `Display` writes it),
+// `rgb(128, 255, 90)`, `rgb(50%, 100%, 35%)` and the CSS colour names, all
+// case-insensitively. Forms with an alpha channel are rejected; parse an
+// `Rgba` for those.
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, false).map(Rgba::color)
+    }
+}
+
+// Everything `Color` accepts, plus `#80FF5A80`, `#8F58`,
+// `rgba(128, 255, 90, 0.5)` (or `rgb` with four arguments, or the alpha as a
+// percentage) and `transparent`. Colours without alpha are opaque.
+impl FromStr for Rgba {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, true)
+    }
+}
+
+fn parse(s: &str, alpha: bool) -> Result<Rgba, ParseColorError> {
+    let start = s.len() - s.trim_start().len();
+    let mut parser = Parser {
+        src: s.trim_end(),
+        pos: start,
+        form: None,
+        alpha,
+    };
+    let rest = parser.rest();
+    if rest.is_empty() {
+        return Err(parser.error(ParseColorErrorKind::Empty));
+    }
+
+    if rest.starts_with('#') {
+        parser.form = Some(ColorForm::Hex);
+        parser.pos += 1;
+        parser.hex(&[3, 4, 6, 8])
+    } else if rest.starts_with("0x") || rest.starts_with("0X") {
+        parser.form = Some(ColorForm::Hex);
+        parser.pos += 2;
+        parser.hex(&[6, 8])
+    } else if rest.contains('(') {
+        parser.form = Some(ColorForm::Function);
+        parser.function()
+    } else {
+        parser.form = Some(ColorForm::Name);
+        parser.name()
+    }
+}
+
+// A hand-rolled cursor over the input. Positions are byte offsets so they can
+// be used to slice the original string.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    form: Option<ColorForm>,
+    // Whether the result may have an alpha channel.
+    alpha: bool,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, kind: ParseColorErrorKind) -> ParseColorError {
+        self.error_at(kind, self.pos)
+    }
+
+    fn error_at(&self, kind: ParseColorErrorKind, position: usize) -> ParseColorError {
+        ParseColorError {
+            kind,
+            form: self.form,
+            position,
+        }
+    }
+
+    fn unexpected(&self) -> ParseColorError {
+        match self.peek() {
+            Some(c) => self.error(ParseColorErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseColorErrorKind::UnexpectedEnd),
+        }
+    }
+
+    // The digits after `#` or `0x`. Three or four digits are shorthand with
+    // each digit doubled, so `#8F5` is `#88FF55`.
+    fn hex(&mut self, lengths: &[usize]) -> Result<Rgba, ParseColorError> {
+        let start = self.pos;
+        let mut digits = Vec::with_capacity(8);
+        for (i, c) in self.rest().char_indices() {
+            match c.to_digit(16) {
+                Some(d) => digits.push(d as u8),
+                None => {
+                    return Err(self.error_at(ParseColorErrorKind::InvalidHexDigit(c), start + i))
+                }
+            }
+        }
+
+        let n = digits.len();
+        if !lengths.contains(&n) {
+            return Err(self.error_at(ParseColorErrorKind::WrongHexLength(n), start));
+        }
+        if !self.alpha && (n == 4 || n == 8) {
+            return Err(self.error_at(ParseColorErrorKind::UnexpectedAlpha, start));
+        }
+        let channels: Vec<u8> = if n <= 4 {
+            digits.iter().map(|d| d * 17).collect()
+        } else {
+            digits.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect()
+        };
+        Ok(Rgba {
+            red: channels[0],
+            green: channels[1],
+            blue: channels[2],
+            alpha: channels.get(3).copied().unwrap_or(255),
+        })
+    }
+
+    // `rgb(r, g, b)` or `rgba(r, g, b, a)`; in CSS the two are synonyms, so
+    // either takes three or four arguments.
+    fn function(&mut self) -> Result<Rgba, ParseColorError> {
+        let start = self.pos;
+        let open = self.rest().find('(').unwrap();
+        let name = self.rest()[..open].trim_end();
+        if !(name.eq_ignore_ascii_case("rgb") || name.eq_ignore_ascii_case("rgba")) {
+            return Err(self.error_at(ParseColorErrorKind::UnknownFunction, start));
+        }
+        self.pos += open + 1;
+
+        let mut args = Vec::with_capacity(4);
+        loop {
+            self.skip_whitespace();
+            args.push(self.argument()?);
+            self.skip_whitespace();
+            if self.eat(')') {
+                break;
+            }
+            if !self.eat(',') {
+                return Err(self.unexpected());
+            }
+        }
+        if self.peek().is_some() {
+            return Err(self.unexpected());
+        }
+
+        match args.len() {
+            3 => {}
+            4 if self.alpha => {}
+            4 => return Err(self.error_at(ParseColorErrorKind::UnexpectedAlpha, args[3].start)),
+            n => return Err(self.error_at(ParseColorErrorKind::WrongArgumentCount(n), start)),
+        }
+
+        let percent = args[0].percent;
+        if let Some(arg) = args[..3].iter().find(|a| a.percent != percent) {
+            return Err(self.error_at(ParseColorErrorKind::MixedUnits, arg.start));
+        }
+        // Channels are 0 to 255, or 0% to 100%; alpha is 0 to 1, or 0% to 100%.
+        let scale = |arg: &Argument, full: f64| {
+            let fraction = if arg.percent { arg.value / 100.0 } else { arg.value / full };
+            if (0.0..=1.0).contains(&fraction) {
+                Ok((fraction * 255.0).round() as u8)
+            } else {
+                Err(self.error_at(ParseColorErrorKind::OutOfRange, arg.start))
+            }
+        };
+        Ok(Rgba {
+            red: scale(&args[0], 255.0)?,
+            green: scale(&args[1], 255.0)?,
+            blue: scale(&args[2], 255.0)?,
+            alpha: args.get(3).map_or(Ok(255), |a| scale(a, 1.0))?,
+        })
+    }
+
+    // A non-negative decimal number, optionally followed by `%`.
+    fn argument(&mut self) -> Result<Argument, ParseColorError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let number = rest.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let len = rest.len() - number.len();
+        if len == 0 {
+            return Err(self.unexpected());
+        }
+        let value: f64 = rest[..len]
+            .parse()
+            .map_err(|_| self.error(ParseColorErrorKind::InvalidNumber))?;
+        self.pos += len;
+        let percent = self.eat('%');
+        Ok(Argument { value, percent, start })
+    }
+
+    fn name(&mut self) -> Result<Rgba, ParseColorError> {
+        let name = self.rest().to_ascii_lowercase();
+        if self.alpha && name == "transparent" {
+            return Ok(Rgba { red: 0, green: 0, blue: 0, alpha: 0 });
+        }
+        match NAMED_COLORS.binary_search_by(|(n, _)| n.cmp(&name.as_str())) {
+            Ok(i) => {
+                let [red, green, blue] = NAMED_COLORS[i].1;
+                Ok(Rgba { red, green, blue, alpha: 255 })
+            }
+            Err(_) => Err(self.error(ParseColorErrorKind::UnknownName)),
+        }
+    }
+}
+
+struct Argument {
+    value: f64,
+    percent: bool,
+    start: usize,
+}
+
+// The CSS Color Module Level 4 named colours, sorted for binary search.
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xF0, 0xF8, 0xFF]),
+    ("antiquewhite", [0xFA, 0xEB, 0xD7]),
+    ("aqua", [0x00, 0xFF, 0xFF]),
+    ("aquamarine", [0x7F, 0xFF, 0xD4]),
+    ("azure", [0xF0, 0xFF, 0xFF]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("bisque", [0xFF, 0xE4, 0xC4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xFF, 0xEB, 0xCD]),
+    ("blue", [0x00, 0x00, 0xFF]),
+    ("blueviolet", [0x8A, 0x2B, 0xE2]),
+    ("brown", [0xA5, 0x2A, 0x2A]),
+    ("burlywood", [0xDE, 0xB8, 0x87]),
+    ("cadetblue", [0x5F, 0x9E, 0xA0]),
+    ("chartreuse", [0x7F, 0xFF, 0x00]),
+    ("chocolate", [0xD2, 0x69, 0x1E]),
+    ("coral", [0xFF, 0x7F, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xED]),
+    ("cornsilk", [0xFF, 0xF8, 0xDC]),
+    ("crimson", [0xDC, 0x14, 0x3C]),
+    ("cyan", [0x00, 0xFF, 0xFF]),
+    ("darkblue", [0x00, 0x00, 0x8B]),
+    ("darkcyan", [0x00, 0x8B, 0x8B]),
+    ("darkgoldenrod", [0xB8, 0x86, 0x0B]),
+    ("darkgray", [0xA9, 0xA9, 0xA9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xA9, 0xA9, 0xA9]),
+    ("darkkhaki", [0xBD, 0xB7, 0x6B]),
+    ("darkmagenta", [0x8B, 0x00, 0x8B]),
+    ("darkolivegreen", [0x55, 0x6B, 0x2F]),
+    ("darkorange", [0xFF, 0x8C, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xCC]),
+    ("darkred", [0x8B, 0x00, 0x00]),
+    ("darksalmon", [0xE9, 0x96, 0x7A]),
+    ("darkseagreen", [0x8F, 0xBC, 0x8F]),
+    ("darkslateblue", [0x48, 0x3D, 0x8B]),
+    ("darkslategray", [0x2F, 0x4F, 0x4F]),
+    ("darkslategrey", [0x2F, 0x4F, 0x4F]),
+    ("darkturquoise", [0x00, 0xCE, 0xD1]),
+    ("darkviolet", [0x94, 0x00, 0xD3]),
+    ("deeppink", [0xFF, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xBF, 0xFF]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1E, 0x90, 0xFF]),
+    ("firebrick", [0xB2, 0x22, 0x22]),
+    ("floralwhite", [0xFF, 0xFA, 0xF0]),
+    ("forestgreen", [0x22, 0x8B, 0x22]),
+    ("fuchsia", [0xFF, 0x00, 0xFF]),
+    ("gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("ghostwhite", [0xF8, 0xF8, 0xFF]),
+    ("gold", [0xFF, 0xD7, 0x00]),
+    ("goldenrod", [0xDA, 0xA5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xAD, 0xFF, 0x2F]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xF0, 0xFF, 0xF0]),
+    ("hotpink", [0xFF, 0x69, 0xB4]),
+    ("indianred", [0xCD, 0x5C, 0x5C]),
+    ("indigo", [0x4B, 0x00, 0x82]),
+    ("ivory", [0xFF, 0xFF, 0xF0]),
+    ("khaki", [0xF0, 0xE6, 0x8C]),
+    ("lavender", [0xE6, 0xE6, 0xFA]),
+    ("lavenderblush", [0xFF, 0xF0, 0xF5]),
+    ("lawngreen", [0x7C, 0xFC, 0x00]),
+    ("lemonchiffon", [0xFF, 0xFA, 0xCD]),
+    ("lightblue", [0xAD, 0xD8, 0xE6]),
+    ("lightcoral", [0xF0, 0x80, 0x80]),
+    ("lightcyan", [0xE0, 0xFF, 0xFF]),
+    ("lightgoldenrodyellow", [0xFA, 0xFA, 0xD2]),
+    ("lightgray", [0xD3, 0xD3, 0xD3]),
+    ("lightgreen", [0x90, 0xEE, 0x90]),
+    ("lightgrey", [0xD3, 0xD3, 0xD3]),
+    ("lightpink", [0xFF, 0xB6, 0xC1]),
+    ("lightsalmon", [0xFF, 0xA0, 0x7A]),
+    ("lightseagreen", [0x20, 0xB2, 0xAA]),
+    ("lightskyblue", [0x87, 0xCE, 0xFA]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xB0, 0xC4, 0xDE]),
+    ("lightyellow", [0xFF, 0xFF, 0xE0]),
+    ("lime", [0x00, 0xFF, 0x00]),
+    ("limegreen", [0x32, 0xCD, 0x32]),
+    ("linen", [0xFA, 0xF0, 0xE6]),
+    ("magenta", [0xFF, 0x00, 0xFF]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xCD, 0xAA]),
+    ("mediumblue", [0x00, 0x00, 0xCD]),
+    ("mediumorchid", [0xBA, 0x55, 0xD3]),
+    ("mediumpurple", [0x93, 0x70, 0xDB]),
+    ("mediumseagreen", [0x3C, 0xB3, 0x71]),
+    ("mediumslateblue", [0x7B, 0x68, 0xEE]),
+    ("mediumspringgreen", [0x00, 0xFA, 0x9A]),
+    ("mediumturquoise", [0x48, 0xD1, 0xCC]),
+    ("mediumvioletred", [0xC7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xF5, 0xFF, 0xFA]),
+    ("mistyrose", [0xFF, 0xE4, 0xE1]),
+    ("moccasin", [0xFF, 0xE4, 0xB5]),
+    ("navajowhite", [0xFF, 0xDE, 0xAD]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xFD, 0xF5, 0xE6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6B, 0x8E, 0x23]),
+    ("orange", [0xFF, 0xA5, 0x00]),
+    ("orangered", [0xFF, 0x45, 0x00]),
+    ("orchid", [0xDA, 0x70, 0xD6]),
+    ("palegoldenrod", [0xEE, 0xE8, 0xAA]),
+    ("palegreen", [0x98, 0xFB, 0x98]),
+    ("paleturquoise", [0xAF, 0xEE, 0xEE]),
+    ("palevioletred", [0xDB, 0x70, 0x93]),
+    ("papayawhip", [0xFF, 0xEF, 0xD5]),
+    ("peachpuff", [0xFF, 0xDA, 0xB9]),
+    ("peru", [0xCD, 0x85, 0x3F]),
+    ("pink", [0xFF, 0xC0, 0xCB]),
+    ("plum", [0xDD, 0xA0, 0xDD]),
+    ("powderblue", [0xB0, 0xE0, 0xE6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xFF, 0x00, 0x00]),
+    ("rosybrown", [0xBC, 0x8F, 0x8F]),
+    ("royalblue", [0x41, 0x69, 0xE1]),
+    ("saddlebrown", [0x8B, 0x45, 0x13]),
+    ("salmon", [0xFA, 0x80, 0x72]),
+    ("sandybrown", [0xF4, 0xA4, 0x60]),
+    ("seagreen", [0x2E, 0x8B, 0x57]),
+    ("seashell", [0xFF, 0xF5, 0xEE]),
+    ("sienna", [0xA0, 0x52, 0x2D]),
+    ("silver", [0xC0, 0xC0, 0xC0]),
+    ("skyblue", [0x87, 0xCE, 0xEB]),
+    ("slateblue", [0x6A, 0x5A, 0xCD]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xFF, 0xFA, 0xFA]),
+    ("springgreen", [0x00, 0xFF, 0x7F]),
+    ("steelblue", [0x46, 0x82, 0xB4]),
+    ("tan", [0xD2, 0xB4, 0x8C]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xD8, 0xBF, 0xD8]),
+    ("tomato", [0xFF, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xE0, 0xD0]),
+    ("violet", [0xEE, 0x82, 0xEE]),
+    ("wheat", [0xF5, 0xDE, 0xB3]),
+    ("white", [0xFF, 0xFF, 0xFF]),
+    ("whitesmoke", [0xF5, 0xF5, 0xF5]),
+    ("yellow", [0xFF, 0xFF, 0x00]),
+    ("yellowgreen", [0x9A, 0xCD, 0x32]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, TextSize, WcagLevel};
+
+    fn hue(c: Color) -> f64 {
+        let oklab = c.to_oklab();
+        oklab.b.atan2(oklab.a).to_degrees()
+    }
+
+    #[test]
+    fn accessible_foreground_keeps_the_hue() {
+        let white = Color { red: 255, green: 255, blue: 255 };
+        let dark = Color { red: 0x1E, green: 0x1E, blue: 0x1E };
+        for (fg, bg, level) in [
+            (Color { red: 0x4A, green: 0x90, blue: 0xE2 }, white, WcagLevel::AA),
+            (Color { red: 0x4A, green: 0x90, blue: 0xE2 }, dark, WcagLevel::AAA),
+            (Color { red: 255, green: 140, blue: 0 }, white, WcagLevel::AA),
+            (Color { red: 200, green: 0, blue: 0 }, dark, WcagLevel::AAA),
+        ] {
+            let fixed = fg.accessible_foreground(bg, level, TextSize::Normal).unwrap();
+            assert!(fixed.contrast(bg).passes(level, TextSize::Normal));
+            assert!((hue(fixed) - hue(fg)).abs() < 2.0, "{} became {}", fg, fixed);
+        }
+    }
+
+    #[test]
+    fn accessible_foreground_keeps_greys_grey() {
+        let white = Color { red: 255, green: 255, blue: 255 };
+        let black = Color { red: 0, green: 0, blue: 0 };
+        for v in [0x55, 0x77, 0x99, 0xBB] {
+            let grey = Color { red: v, green: v, blue: v };
+            for (bg, level) in [(white, WcagLevel::AA), (black, WcagLevel::AAA)] {
+                let fixed = grey.accessible_foreground(bg, level, TextSize::Normal).unwrap();
+                assert!(fixed.red == fixed.green && fixed.green == fixed.blue, "{}", fixed);
+            }
+        }
+        let fixed = Color { red: 0x77, green: 0x77, blue: 0x77 }
+            .accessible_foreground(white, WcagLevel::AA, TextSize::Normal);
+        assert_eq!(fixed, Some(Color { red: 0x76, green: 0x76, blue: 0x76 }));
+    }
+}
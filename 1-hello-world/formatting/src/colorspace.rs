@@ -0,0 +1,305 @@
+use std::f64::consts::PI;
+
+use crate::color::Color;
+
+// sRGB with the transfer curve taken off, so that the channels are
+// proportional to light intensity. Each is 0 to 1 inside the sRGB gamut.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LinearRgb {
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+}
+
+// CIE 1931 XYZ relative to the D65 white point, scaled so white has `y = 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+// CIELAB under D65: `l` is lightness from 0 to 100, `a` runs green to red and
+// `b` blue to yellow, both roughly -128 to 127 for sRGB colours.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+// Björn Ottosson's OKLab: like CIELAB but more even in hue and with `l` from
+// 0 to 1. Straight lines in it make smooth, even-looking gradients.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+// The D65 white point in XYZ.
+const WHITE: Xyz = Xyz {
+    x: 0.95047,
+    y: 1.0,
+    z: 1.08883,
+};
+
+impl From<Color> for LinearRgb {
+    fn from(color: Color) -> Self {
+        let linear = |channel: u8| {
+            let c = channel as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        LinearRgb {
+            red: linear(color.red),
+            green: linear(color.green),
+            blue: linear(color.blue),
+        }
+    }
+}
+
+// Channels outside the gamut are clipped to it.
+impl From<LinearRgb> for Color {
+    fn from(rgb: LinearRgb) -> Self {
+        let encode = |c: f64| {
+            let c = c.clamp(0.0, 1.0);
+            let c = if c <= 0.0031308 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c * 255.0).round() as u8
+        };
+        Color {
+            red: encode(rgb.red),
+            green: encode(rgb.green),
+            blue: encode(rgb.blue),
+        }
+    }
+}
+
+impl From<LinearRgb> for Xyz {
+    fn from(c: LinearRgb) -> Self {
+        Xyz {
+            x: 0.4124564 * c.red + 0.3575761 * c.green + 0.1804375 * c.blue,
+            y: 0.2126729 * c.red + 0.7151522 * c.green + 0.0721750 * c.blue,
+            z: 0.0193339 * c.red + 0.1191920 * c.green + 0.9503041 * c.blue,
+        }
+    }
+}
+
+impl From<Xyz> for LinearRgb {
+    fn from(c: Xyz) -> Self {
+        LinearRgb {
+            red: 3.2404542 * c.x - 1.5371385 * c.y - 0.4985314 * c.z,
+            green: -0.9692660 * c.x + 1.8760108 * c.y + 0.0415560 * c.z,
+            blue: 0.0556434 * c.x - 0.2040259 * c.y + 1.0572252 * c.z,
+        }
+    }
+}
+
+// CIELAB's cube root, with a straight segment near black where the root's
+// slope would be infinite.
+const DELTA: f64 = 6.0 / 29.0;
+
+impl From<Xyz> for Lab {
+    fn from(c: Xyz) -> Self {
+        let f = |t: f64| {
+            if t > DELTA.powi(3) {
+                t.cbrt()
+            } else {
+                t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+            }
+        };
+        let (fx, fy, fz) = (f(c.x / WHITE.x), f(c.y / WHITE.y), f(c.z / WHITE.z));
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(c: Lab) -> Self {
+        let f_inv = |t: f64| {
+            if t > DELTA {
+                t.powi(3)
+            } else {
+                3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+            }
+        };
+        let fy = (c.l + 16.0) / 116.0;
+        Xyz {
+            x: WHITE.x * f_inv(fy + c.a / 500.0),
+            y: WHITE.y * f_inv(fy),
+            z: WHITE.z * f_inv(fy - c.b / 200.0),
+        }
+    }
+}
+
+impl From<LinearRgb> for Oklab {
+    fn from(c: LinearRgb) -> Self {
+        let l = 0.4122214708 * c.red + 0.5363325363 * c.green + 0.0514459929 * c.blue;
+        let m = 0.2119034982 * c.red + 0.6806995451 * c.green + 0.1073969566 * c.blue;
+        let s = 0.0883024619 * c.red + 0.2817188376 * c.green + 0.6299787005 * c.blue;
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+}
+
+impl From<Oklab> for LinearRgb {
+    fn from(c: Oklab) -> Self {
+        let l = (c.l + 0.3963377774 * c.a + 0.2158037573 * c.b).powi(3);
+        let m = (c.l - 0.1055613458 * c.a - 0.0638541728 * c.b).powi(3);
+        let s = (c.l - 0.0894841775 * c.a - 1.2914855480 * c.b).powi(3);
+        LinearRgb {
+            red: 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            green: -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            blue: -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        }
+    }
+}
+
+impl Lab {
+    // The CIE 1976 colour difference: plain distance in CIELAB. A difference
+    // of about 2.3 is just noticeable.
+    pub fn delta_e76(self, other: Lab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+
+    // The CIEDE2000 colour difference, which corrects CIELAB's unevenness in
+    // the blues and for saturated colours. A difference of 1 is about the
+    // smallest most people can see.
+    pub fn delta_e2000(self, other: Lab) -> f64 {
+        let (l1, a1, b1) = (self.l, self.a, self.b);
+        let (l2, a2, b2) = (other.l, other.a, other.b);
+        let pow7 = |x: f64| x.powi(7);
+
+        let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+        let g = 0.5 * (1.0 - (pow7(c_mean) / (pow7(c_mean) + pow7(25.0))).sqrt());
+        let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+        let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+        let hue = |a: f64, b: f64| {
+            if a == 0.0 && b == 0.0 {
+                0.0
+            } else {
+                b.atan2(a).to_degrees().rem_euclid(360.0)
+            }
+        };
+        let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+        let delta_l = l2 - l1;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 <= h1 {
+            h2 - h1 + 360.0
+        } else {
+            h2 - h1 - 360.0
+        };
+        let delta_big_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        let l_mean = (l1 + l2) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let cos_deg = |d: f64| d.to_radians().cos();
+        let t = 1.0 - 0.17 * cos_deg(h_mean - 30.0)
+            + 0.24 * cos_deg(2.0 * h_mean)
+            + 0.32 * cos_deg(3.0 * h_mean + 6.0)
+            - 0.20 * cos_deg(4.0 * h_mean - 63.0);
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let r_c = 2.0 * (pow7(c_mean) / (pow7(c_mean) + pow7(25.0))).sqrt();
+        let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+        let s_c = 1.0 + 0.045 * c_mean;
+        let s_h = 1.0 + 0.015 * c_mean * t;
+        let r_t = -(2.0 * delta_theta * PI / 180.0).sin() * r_c;
+
+        let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_big_h / s_h);
+        (l * l + c * c + h * h + r_t * c * h).sqrt()
+    }
+}
+
+impl Color {
+    pub fn to_linear_rgb(self) -> LinearRgb {
+        LinearRgb::from(self)
+    }
+
+    pub fn to_xyz(self) -> Xyz {
+        Xyz::from(self.to_linear_rgb())
+    }
+
+    pub fn to_lab(self) -> Lab {
+        Lab::from(self.to_xyz())
+    }
+
+    pub fn to_oklab(self) -> Oklab {
+        Oklab::from(self.to_linear_rgb())
+    }
+
+    // The nearest sRGB colour; anything outside the gamut is clipped.
+    pub fn from_lab(lab: Lab) -> Color {
+        Color::from(LinearRgb::from(Xyz::from(lab)))
+    }
+
+    pub fn from_oklab(oklab: Oklab) -> Color {
+        Color::from(LinearRgb::from(oklab))
+    }
+
+    pub fn delta_e76(self, other: Color) -> f64 {
+        self.to_lab().delta_e76(other.to_lab())
+    }
+
+    pub fn delta_e2000(self, other: Color) -> f64 {
+        self.to_lab().delta_e2000(other.to_lab())
+    }
+}
+
+// `n` colours evenly spaced along the path through `stops`, interpolated in
+// OKLab, starting at the first stop and ending at the last. Blending there
+// avoids the muddy, dark midpoints of mixing sRGB channels directly.
+pub fn gradient(stops: &[Color], n: usize) -> Vec<Color> {
+    let points: Vec<Oklab> = stops.iter().map(|c| c.to_oklab()).collect();
+    match (points.len(), n) {
+        (0, _) | (_, 0) => return Vec::new(),
+        (1, _) | (_, 1) => return vec![stops[0]; n],
+        _ => {}
+    }
+
+    let segments = (points.len() - 1) as f64;
+    (0..n)
+        .map(|i| {
+            // Where this colour falls along the whole path, as a segment
+            // index plus a fraction of the way along it.
+            let t = i as f64 / (n - 1) as f64 * segments;
+            let segment = (t.floor() as usize).min(points.len() - 2);
+            let f = t - segment as f64;
+            let (p, q) = (points[segment], points[segment + 1]);
+            Color::from_oklab(Oklab {
+                l: p.l + (q.l - p.l) * f,
+                a: p.a + (q.a - p.a) * f,
+                b: p.b + (q.b - p.b) * f,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use crate::color::Color;
+use crate::colorspace::LinearRgb;
+use crate::ppm::{Image, PpmError};
+
+// The three kinds of dichromacy, each missing one type of cone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deficiency {
+    // No long-wavelength (red) cones.
+    Protanopia,
+    // No medium-wavelength (green) cones.
+    Deuteranopia,
+    // No short-wavelength (blue) cones.
+    Tritanopia,
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 3] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+    ];
+}
+
+type Matrix = [[f64; 3]; 3];
+
+// Protanopia and deuteranopia use Viénot, Brettel and Mollon (1999), which
+// projects every colour onto one plane. For tritanopia that plane is a poor
+// fit, so Brettel, Viénot and Mollon (1997) is used, with two half-planes
+// split by `TRITAN_SEPARATION`. All matrices act on linear sRGB and were
+// derived for the sRGB primaries by the DaltonLens project.
+const PROTAN: Matrix = [
+    [0.11238, 0.88762, 0.00000],
+    [0.11238, 0.88762, 0.00000],
+    [0.00401, -0.00401, 1.00000],
+];
+const DEUTAN: Matrix = [
+    [0.29275, 0.70725, 0.00000],
+    [0.29275, 0.70725, 0.00000],
+    [-0.02234, 0.02234, 1.00000],
+];
+const TRITAN_1: Matrix = [
+    [1.01277, 0.13548, -0.14826],
+    [-0.01243, 0.86812, 0.14431],
+    [0.07589, 0.80500, 0.11911],
+];
+const TRITAN_2: Matrix = [
+    [0.93678, 0.18979, -0.12657],
+    [0.06154, 0.81526, 0.12320],
+    [-0.37562, 1.12767, 0.24796],
+];
+const TRITAN_SEPARATION: [f64; 3] = [0.03901, -0.02788, -0.01113];
+
+impl Color {
+    // How the colour looks with the given deficiency. `severity` runs from 0
+    // (normal vision, the colour unchanged) to 1 (full dichromacy); values in
+    // between blend the two in linear light, a simple model of anomalous
+    // trichromacy.
+    pub fn simulate(self, deficiency: Deficiency, severity: f64) -> Color {
+        let LinearRgb { red, green, blue } = self.to_linear_rgb();
+        let rgb = [red, green, blue];
+        let matrix = match deficiency {
+            Deficiency::Protanopia => &PROTAN,
+            Deficiency::Deuteranopia => &DEUTAN,
+            Deficiency::Tritanopia => {
+                let side: f64 = (0..3).map(|i| rgb[i] * TRITAN_SEPARATION[i]).sum();
+                if side >= 0.0 {
+                    &TRITAN_1
+                } else {
+                    &TRITAN_2
+                }
+            }
+        };
+
+        let severity = severity.clamp(0.0, 1.0);
+        let channel = |i: usize| {
+            let simulated: f64 = (0..3).map(|j| matrix[i][j] * rgb[j]).sum();
+            severity * simulated + (1.0 - severity) * rgb[i]
+        };
+        Color::from(LinearRgb {
+            red: channel(0),
+            green: channel(1),
+            blue: channel(2),
+        })
+    }
+}
+
+impl Image {
+    pub fn simulate(&self, deficiency: Deficiency, severity: f64) -> Image {
+        self.map(|pixel| pixel.simulate(deficiency, severity))
+    }
+}
+
+// Batch mode: writes `input` as each deficiency would see it into
+// `output_dir`, as `<name>-protanopia.ppm` and so on. Returns the paths
+// written.
+pub fn simulate_ppm<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output_dir: Q,
+    severity: f64,
+) -> Result<Vec<PathBuf>, PpmError> {
+    let input = input.as_ref();
+    let image = Image::load_ppm(input)?;
+    let stem = input.file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+
+    let mut written = Vec::new();
+    for deficiency in Deficiency::ALL {
+        let name = format!("{}-{}.ppm", stem, format!("{:?}", deficiency).to_lowercase());
+        let path = output_dir.as_ref().join(name);
+        image.simulate(deficiency, severity).save_ppm(&path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
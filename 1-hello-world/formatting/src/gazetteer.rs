@@ -0,0 +1,181 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::city::{City, OwnedCity};
+
+// What was wrong with one record of a gazetteer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    // A quoted field with no closing quote.
+    UnterminatedQuote,
+    // Fewer fields than the header promised.
+    MissingField(&'static str),
+    // A latitude or longitude that is not a number.
+    InvalidNumber(&'static str),
+    // A latitude or longitude outside the globe.
+    OutOfRange(&'static str),
+}
+
+#[derive(Debug)]
+pub enum GazetteerError {
+    Io(io::Error),
+    // The file has no header row.
+    MissingHeader,
+    // The header has no column that looks like the name, latitude or
+    // longitude.
+    MissingColumn(&'static str),
+    // A record that could not be read, by 1-based line number.
+    Record { line: usize, kind: RecordErrorKind },
+}
+
+impl Display for GazetteerError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GazetteerError::Io(e) => write!(f, "cannot read gazetteer: {}", e),
+            GazetteerError::MissingHeader => write!(f, "gazetteer has no header row"),
+            GazetteerError::MissingColumn(column) => {
+                write!(f, "gazetteer has no {} column", column)
+            }
+            GazetteerError::Record { line, kind } => {
+                write!(f, "line {}: ", line)?;
+                match kind {
+                    RecordErrorKind::UnterminatedQuote => write!(f, "unterminated quoted field"),
+                    RecordErrorKind::MissingField(field) => write!(f, "missing {}", field),
+                    RecordErrorKind::InvalidNumber(field) => write!(f, "invalid {}", field),
+                    RecordErrorKind::OutOfRange(field) => write!(f, "{} out of range", field),
+                }
+            }
+        }
+    }
+}
+
+impl Error for GazetteerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GazetteerError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GazetteerError {
+    fn from(e: io::Error) -> Self {
+        GazetteerError::Io(e)
+    }
+}
+
+// Header names recognised for each column, compared case-insensitively.
+const NAME_COLUMNS: &[&str] = &["name", "city", "asciiname", "place"];
+const LAT_COLUMNS: &[&str] = &["lat", "latitude"];
+const LON_COLUMNS: &[&str] = &["lon", "lng", "long", "longitude"];
+
+pub fn load_gazetteer<P: AsRef<Path>>(path: P) -> Result<Vec<OwnedCity>, GazetteerError> {
+    read_gazetteer(BufReader::new(File::open(path)?))
+}
+
+// Reads cities from comma- or tab-separated text with a header row; the
+// header decides which, as well as which columns hold the name, latitude and
+// longitude. Other columns are ignored. Comma-separated fields may be quoted
+// as in RFC 4180, though a record must fit on one line. Blank lines are
+// skipped.
+pub fn read_gazetteer<R: BufRead>(reader: R) -> Result<Vec<OwnedCity>, GazetteerError> {
+    let mut lines = reader.lines().enumerate();
+    let header = loop {
+        match lines.next() {
+            Some((_, line)) => {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    break line;
+                }
+            }
+            None => return Err(GazetteerError::MissingHeader),
+        }
+    };
+
+    let delimiter = if header.contains('\t') { '\t' } else { ',' };
+    let columns = split_record(&header, delimiter).ok_or(GazetteerError::MissingHeader)?;
+    let find = |names: &[&str], column: &'static str| {
+        columns
+            .iter()
+            .position(|c| names.iter().any(|n| c.trim().eq_ignore_ascii_case(n)))
+            .ok_or(GazetteerError::MissingColumn(column))
+    };
+    let name = find(NAME_COLUMNS, "name")?;
+    let lat = find(LAT_COLUMNS, "latitude")?;
+    let lon = find(LON_COLUMNS, "longitude")?;
+
+    let mut cities = Vec::new();
+    for (i, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |kind| GazetteerError::Record { line: i + 1, kind };
+
+        let fields = split_record(&line, delimiter)
+            .ok_or_else(|| error(RecordErrorKind::UnterminatedQuote))?;
+        let field = |index: usize, what: &'static str| {
+            fields
+                .get(index)
+                .map(|f| f.trim())
+                .ok_or_else(|| error(RecordErrorKind::MissingField(what)))
+        };
+        let degrees = |index: usize, what: &'static str, limit: f32| {
+            let value: f32 = field(index, what)?
+                .parse()
+                .map_err(|_| error(RecordErrorKind::InvalidNumber(what)))?;
+            if value.abs() <= limit {
+                Ok(value)
+            } else {
+                Err(error(RecordErrorKind::OutOfRange(what)))
+            }
+        };
+
+        cities.push(City {
+            name: field(name, "name")?.to_string(),
+            lat: degrees(lat, "latitude", 90.0)?,
+            lon: degrees(lon, "longitude", 180.0)?,
+        });
+    }
+    Ok(cities)
+}
+
+// Splits one line into fields. Tab-separated files have no quoting; in
+// comma-separated ones a field may be wrapped in double quotes, with `""`
+// standing for a quote inside it. Returns `None` for an unterminated quote.
+fn split_record(line: &str, delimiter: char) -> Option<Vec<String>> {
+    if delimiter == '\t' {
+        return Some(line.split('\t').map(str::to_string).collect());
+    }
+
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next()? {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+        }
+        // Unquoted fields run up to the next comma; so does any stray text
+        // after a closing quote.
+        while let Some(c) = chars.next_if(|&c| c != ',') {
+            field.push(c);
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Some(fields);
+        }
+    }
+}
@@ -0,0 +1,598 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+// Mean Earth radius (IUGG), used by the spherical formulas.
+pub const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+// The WGS-84 ellipsoid, used by Vincenty's formula.
+const WGS84_A: f64 = 6_378_137.0;
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+const WGS84_B: f64 = WGS84_A * (1.0 - WGS84_F);
+
+// A latitude/longitude pair in degrees, north and east positive.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Coordinates {
+    pub fn new(lat: f64, lon: f64) -> Coordinates {
+        Coordinates { lat, lon }
+    }
+
+    // Whether both the latitude and the longitude are finite, rather than
+    // NaN or infinite.
+    pub fn is_finite(self) -> bool {
+        self.lat.is_finite() && self.lon.is_finite()
+    }
+
+    pub fn distance_to(self, other: Coordinates) -> Distance {
+        let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_phi = phi2 - phi1;
+        let d_lambda = (other.lon - self.lon).to_radians();
+
+        let h = (d_phi / 2.0).sin().powi(2)
+            + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+        // `min` guards against `h` creeping past 1 through rounding.
+        let angle = 2.0 * h.sqrt().min(1.0).asin();
+        Distance::from_meters(EARTH_RADIUS_M * angle)
+    }
+
+    pub fn vincenty_distance_to(self, other: Coordinates) -> Option<Distance> {
+        let (a, b, f) = (WGS84_A, WGS84_B, WGS84_F);
+
+        // Reduced latitudes on the auxiliary sphere.
+        let u1 = ((1.0 - f) * self.lat.to_radians().tan()).atan();
+        let u2 = ((1.0 - f) * other.lat.to_radians().tan()).atan();
+        let (sin_u1, cos_u1) = u1.sin_cos();
+        let (sin_u2, cos_u2) = u2.sin_cos();
+        let l = (other.lon - self.lon).to_radians();
+
+        let mut lambda = l;
+        for _ in 0..200 {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+                + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+            .sqrt();
+            if sin_sigma == 0.0 {
+                // The points coincide.
+                return Some(Distance::from_meters(0.0));
+            }
+
+            let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            let sigma = sin_sigma.atan2(cos_sigma);
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+            // On the equator `cos²α` is zero and the term drops out.
+            let cos_2sigma_m = if cos_sq_alpha == 0.0 {
+                0.0
+            } else {
+                cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+            };
+            let c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+
+            let previous = lambda;
+            lambda = l
+                + (1.0 - c)
+                    * f
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos_2sigma_m
+                                + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+            if (lambda - previous).abs() < 1e-12 {
+                let u_sq = cos_sq_alpha * (a * a - b * b) / (b * b);
+                let big_a = 1.0
+                    + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+                let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+                let delta_sigma = big_b
+                    * sin_sigma
+                    * (cos_2sigma_m
+                        + big_b / 4.0
+                            * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                                - big_b / 6.0
+                                    * cos_2sigma_m
+                                    * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                    * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+                return Some(Distance::from_meters(b * big_a * (sigma - delta_sigma)));
+            }
+        }
+        None
+    }
+
+    pub fn initial_bearing_to(self, other: Coordinates) -> f64 {
+        let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lambda = (other.lon - self.lon).to_radians();
+
+        let y = d_lambda.sin() * phi2.cos();
+        let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * d_lambda.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    pub fn midpoint(self, other: Coordinates) -> Coordinates {
+        let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+        let lambda1 = self.lon.to_radians();
+        let d_lambda = (other.lon - self.lon).to_radians();
+
+        let bx = phi2.cos() * d_lambda.cos();
+        let by = phi2.cos() * d_lambda.sin();
+        let phi = (phi1.sin() + phi2.sin()).atan2(((phi1.cos() + bx).powi(2) + by * by).sqrt());
+        let lambda = lambda1 + by.atan2(phi1.cos() + bx);
+        Coordinates::new(phi.to_degrees(), normalize_longitude(lambda.to_degrees()))
+    }
+
+    pub fn destination(self, bearing: f64, distance: Distance) -> Coordinates {
+        let delta = distance.meters() / EARTH_RADIUS_M;
+        let theta = bearing.to_radians();
+        let phi1 = self.lat.to_radians();
+        let lambda1 = self.lon.to_radians();
+
+        let phi2 = (phi1.sin() * delta.cos() + phi1.cos() * delta.sin() * theta.cos()).asin();
+        let lambda2 = lambda1
+            + (theta.sin() * delta.sin() * phi1.cos()).atan2(delta.cos() - phi1.sin() * phi2.sin());
+        Coordinates::new(phi2.to_degrees(), normalize_longitude(lambda2.to_degrees()))
+    }
+}
+
+// Returned where coordinates have to be real numbers but one of them is NaN
+// or infinite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NonFiniteCoordinates(pub Coordinates);
+
+impl Display for NonFiniteCoordinates {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "coordinates {}, {} are not finite", self.0.lat, self.0.lon)
+    }
+}
+
+impl Error for NonFiniteCoordinates {}
+
+// Wrap a longitude into `[-180, 180)`.
+pub fn normalize_longitude(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+// The area between two parallels and two meridians. `min` is the south-west
+// corner and `max` the north-east one.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoundingBox {
+    pub min: Coordinates,
+    pub max: Coordinates,
+}
+
+impl BoundingBox {
+    pub fn center(&self) -> Coordinates {
+        Coordinates::new(
+            (self.min.lat + self.max.lat) / 2.0,
+            (self.min.lon + self.max.lon) / 2.0,
+        )
+    }
+
+    pub fn contains(&self, at: Coordinates) -> bool {
+        self.min.lat <= at.lat
+            && at.lat <= self.max.lat
+            && self.min.lon <= at.lon
+            && at.lon <= self.max.lon
+    }
+}
+
+// `53.348°N 6.260°W`, like `City`. The precision applies to the degrees;
+// `{:#}` writes degrees, minutes and seconds instead, `53°20'52"N 6°15'35"W`,
+// with the precision applying to the seconds.
+impl Display for Coordinates {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let hemispheres = [(self.lat, 'N', 'S'), (self.lon, 'E', 'W')];
+        for (i, (value, positive, negative)) in hemispheres.into_iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            let hemisphere = if value >= 0.0 { positive } else { negative };
+            if f.alternate() {
+                write_dms(f, value.abs(), f.precision().unwrap_or(0))?;
+            } else if let Some(p) = f.precision() {
+                write!(f, "{:.*}°", p, value.abs())?;
+            } else {
+                write!(f, "{}°", value.abs())?;
+            }
+            write!(f, "{}", hemisphere)?;
+        }
+        Ok(())
+    }
+}
+
+// Rounding is done once, on the total number of seconds, so that 59.9" never
+// shows up as 60".
+fn write_dms(f: &mut Formatter, degrees: f64, precision: usize) -> fmt::Result {
+    let scale = 10u64.pow(precision as u32);
+    let total = (degrees * 3600.0 * scale as f64).round() as u64;
+    let (whole, fraction) = (total / scale, total % scale);
+    write!(f, "{}°{}'{}", whole / 3600, whole / 60 % 60, whole % 60)?;
+    if precision > 0 {
+        write!(f, ".{:0width$}", fraction, width = precision)?;
+    }
+    f.write_str("\"")
+}
+
+// What went wrong while parsing `Coordinates`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseCoordinatesErrorKind {
+    // The input was empty or only whitespace.
+    Empty,
+    // Something that started like a number could not be read as one.
+    InvalidNumber,
+    // A character that does not fit the grammar at this point.
+    UnexpectedChar(char),
+    // The input stopped before both coordinates were read.
+    UnexpectedEnd,
+    // Minutes or seconds of 60 or more.
+    InvalidMinutesOrSeconds,
+    // A coordinate with both a sign and a hemisphere letter, as in `-53°N`.
+    ConflictingSign,
+    // Two latitudes or two longitudes, as in `53°N 6°S`.
+    DuplicateAxis,
+    // A latitude outside `[-90, 90]`.
+    LatitudeOutOfRange,
+    // A longitude outside `[-180, 180]`.
+    LongitudeOutOfRange,
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCoordinatesError {
+    kind: ParseCoordinatesErrorKind,
+    position: usize,
+}
+
+impl ParseCoordinatesError {
+    pub fn kind(&self) -> ParseCoordinatesErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseCoordinatesError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use ParseCoordinatesErrorKind::*;
+
+        match self.kind {
+            Empty => return write!(f, "cannot parse coordinates from an empty string"),
+            InvalidNumber => write!(f, "invalid number")?,
+            UnexpectedChar(c) => write!(f, "unexpected character `{}`", c)?,
+            UnexpectedEnd => write!(f, "unexpected end of input")?,
+            InvalidMinutesOrSeconds => write!(f, "minutes and seconds must be below 60")?,
+            ConflictingSign => write!(f, "coordinate has both a sign and a hemisphere")?,
+            DuplicateAxis => write!(f, "expected one latitude and one longitude")?,
+            LatitudeOutOfRange => write!(f, "latitude must be between -90 and 90")?,
+            LongitudeOutOfRange => write!(f, "longitude must be between -180 and 180")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseCoordinatesError {}
+
+// Reads a latitude and a longitude separated by whitespace or a comma. Each
+// may be signed decimal degrees (`53.348, -6.26`), carry a hemisphere letter
+// as `Display` writes it (`53.348°N 6.260°W`), or be given in degrees,
+// minutes and seconds (`53°20'52"N 6°15'35"W`; `′` and `″` work too). With
+// hemisphere letters the longitude may come first.
+impl FromStr for Coordinates {
+    type Err = ParseCoordinatesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { src: s, pos: 0 };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Err(parser.error(ParseCoordinatesErrorKind::Empty));
+        }
+
+        let first = parser.angle()?;
+        parser.skip_whitespace();
+        if parser.eat(',') {
+            parser.skip_whitespace();
+        }
+        let second = parser.angle()?;
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return Err(parser.unexpected());
+        }
+
+        let (lat, lon) = match (first.axis, second.axis) {
+            (Some(Axis::Latitude), Some(Axis::Latitude))
+            | (Some(Axis::Longitude), Some(Axis::Longitude)) => {
+                return Err(ParseCoordinatesError {
+                    kind: ParseCoordinatesErrorKind::DuplicateAxis,
+                    position: second.start,
+                })
+            }
+            (Some(Axis::Longitude), _) | (None, Some(Axis::Latitude)) => (second, first),
+            _ => (first, second),
+        };
+
+        let out_of_range = |angle: &Angle, limit: f64, kind| {
+            if angle.value.abs() > limit {
+                Err(ParseCoordinatesError { kind, position: angle.start })
+            } else {
+                Ok(())
+            }
+        };
+        out_of_range(&lat, 90.0, ParseCoordinatesErrorKind::LatitudeOutOfRange)?;
+        out_of_range(&lon, 180.0, ParseCoordinatesErrorKind::LongitudeOutOfRange)?;
+
+        Ok(Coordinates::new(lat.value, lon.value))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Latitude,
+    Longitude,
+}
+
+// One coordinate as read, with its hemisphere letter (if any) already
+// applied to the sign.
+struct Angle {
+    value: f64,
+    axis: Option<Axis>,
+    start: usize,
+}
+
+// A hand-rolled cursor over the input. Positions are byte offsets so they can
+// be used to slice the original string.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_any(&mut self, cs: &[char]) -> Option<char> {
+        cs.iter().copied().find(|&c| self.eat(c))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, kind: ParseCoordinatesErrorKind) -> ParseCoordinatesError {
+        ParseCoordinatesError { kind, position: self.pos }
+    }
+
+    fn unexpected(&self) -> ParseCoordinatesError {
+        match self.peek() {
+            Some(c) => self.error(ParseCoordinatesErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseCoordinatesErrorKind::UnexpectedEnd),
+        }
+    }
+
+    // `[sign] degrees [° [minutes ' [seconds "]]] [hemisphere]`.
+    fn angle(&mut self) -> Result<Angle, ParseCoordinatesError> {
+        let start = self.pos;
+        let sign = self.eat_any(&['+', '-']);
+        let Some(mut value) = self.number()? else {
+            return Err(self.unexpected());
+        };
+
+        if self.eat('°') {
+            for (divisor, marks) in [(60.0, &['\'', '′']), (3600.0, &['"', '″'])] {
+                let Some(part) = self.marked_number(marks)? else {
+                    break;
+                };
+                value += part / divisor;
+            }
+        }
+
+        let before_hemisphere = self.pos;
+        self.skip_whitespace();
+        let (axis, negative) = match self.eat_any(&['N', 'S', 'E', 'W']) {
+            Some(_) if sign.is_some() => {
+                return Err(ParseCoordinatesError {
+                    kind: ParseCoordinatesErrorKind::ConflictingSign,
+                    position: start,
+                });
+            }
+            Some('N') => (Some(Axis::Latitude), false),
+            Some('S') => (Some(Axis::Latitude), true),
+            Some('E') => (Some(Axis::Longitude), false),
+            Some(_) => (Some(Axis::Longitude), true),
+            None => {
+                self.pos = before_hemisphere;
+                (None, sign == Some('-'))
+            }
+        };
+
+        Ok(Angle {
+            value: if negative { -value } else { value },
+            axis,
+            start,
+        })
+    }
+
+    // Minutes or seconds: a number below 60 followed by one of `marks`. If the
+    // mark is missing, nothing is consumed, so in `53° 6°E` the `6` is left
+    // for the longitude.
+    fn marked_number(&mut self, marks: &[char]) -> Result<Option<f64>, ParseCoordinatesError> {
+        let before = self.pos;
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.number()? {
+            Some(x) if self.eat_any(marks).is_some() => {
+                if x < 60.0 {
+                    Ok(Some(x))
+                } else {
+                    Err(ParseCoordinatesError {
+                        kind: ParseCoordinatesErrorKind::InvalidMinutesOrSeconds,
+                        position: start,
+                    })
+                }
+            }
+            _ => {
+                self.pos = before;
+                Ok(None)
+            }
+        }
+    }
+
+    // An unsigned decimal number with an optional fraction. Returns `None`
+    // without consuming anything if no number starts here.
+    fn number(&mut self) -> Result<Option<f64>, ParseCoordinatesError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let rest = p.rest();
+            let n = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            p.pos += n;
+            n
+        };
+
+        let mut mantissa = digits(self);
+        if self.eat('.') {
+            mantissa += digits(self);
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        if mantissa == 0 {
+            self.pos = start;
+            return Err(self.error(ParseCoordinatesErrorKind::InvalidNumber));
+        }
+
+        self.src[start..self.pos]
+            .parse()
+            .map(Some)
+            .map_err(|_| ParseCoordinatesError {
+                kind: ParseCoordinatesErrorKind::InvalidNumber,
+                position: start,
+            })
+    }
+}
+
+// Units a `Distance` can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceUnit {
+    Kilometers,
+    Miles,
+    NauticalMiles,
+}
+
+impl DistanceUnit {
+    fn meters(self) -> f64 {
+        match self {
+            DistanceUnit::Kilometers => 1000.0,
+            DistanceUnit::Miles => 1609.344,
+            DistanceUnit::NauticalMiles => 1852.0,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            DistanceUnit::Kilometers => "km",
+            DistanceUnit::Miles => "mi",
+            DistanceUnit::NauticalMiles => "nmi",
+        }
+    }
+}
+
+// A length along the Earth's surface. Stored in metres; `Display` shows
+// kilometres, and `display` picks another unit.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Distance(f64);
+
+impl Distance {
+    pub fn from_meters(meters: f64) -> Distance {
+        Distance(meters)
+    }
+
+    pub fn from_km(km: f64) -> Distance {
+        Distance(km * 1000.0)
+    }
+
+    pub fn from_miles(miles: f64) -> Distance {
+        Distance(miles * DistanceUnit::Miles.meters())
+    }
+
+    pub fn meters(self) -> f64 {
+        self.0
+    }
+
+    pub fn km(self) -> f64 {
+        self.in_unit(DistanceUnit::Kilometers)
+    }
+
+    pub fn miles(self) -> f64 {
+        self.in_unit(DistanceUnit::Miles)
+    }
+
+    pub fn in_unit(self, unit: DistanceUnit) -> f64 {
+        self.0 / unit.meters()
+    }
+
+    pub fn display(self, unit: DistanceUnit) -> DistanceDisplay {
+        DistanceDisplay {
+            distance: self,
+            unit,
+        }
+    }
+}
+
+// Shows the distance in kilometres, e.g. `{:.1}` gives `1267.0 km`.
+impl Display for Distance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.display(DistanceUnit::Kilometers), f)
+    }
+}
+
+pub struct DistanceDisplay {
+    distance: Distance,
+    unit: DistanceUnit,
+}
+
+impl Display for DistanceDisplay {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.distance.in_unit(self.unit);
+        match f.precision() {
+            Some(p) => write!(f, "{:.*} {}", p, value, self.unit.symbol()),
+            None => write!(f, "{} {}", value, self.unit.symbol()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Coordinates;
+
+    #[test]
+    fn dublin_to_oslo_matches_reference_distances() {
+        let dublin = Coordinates::new(53.347778, -6.259722);
+        let oslo = Coordinates::new(59.95, 10.75);
+        // About 1267 km on the mean-radius sphere, and 1270.5 km along the
+        // WGS-84 geodesic.
+        assert!((dublin.distance_to(oslo).km() - 1267.0).abs() < 1.0);
+        let ellipsoidal = dublin.vincenty_distance_to(oslo).unwrap();
+        assert!((ellipsoidal.km() - 1270.5).abs() < 1.0);
+        assert_eq!(dublin.distance_to(oslo), oslo.distance_to(dublin));
+    }
+}
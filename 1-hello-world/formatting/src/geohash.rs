@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::city::{City, NonFiniteCity};
+use crate::geo::{normalize_longitude, BoundingBox, Coordinates, NonFiniteCoordinates};
+
+// Geohash's base 32 alphabet: the digits and lowercase letters, minus `a`,
+// `i`, `l` and `o`.
+const ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+// Twelve characters pin a point down to a few centimetres, which is about as
+// far as `f64` degrees go.
+pub const MAX_PRECISION: usize = 12;
+
+// A geohash: a cell of the globe named by a string, where every extra
+// character splits the cell into 32 and a shorter prefix names a cell that
+// contains it. Each character carries five bits, alternately halving the
+// longitude and latitude ranges, longitude first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Geohash(String);
+
+// The eight cells around a geohash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    // How many cells to step north and east.
+    fn offset(self) -> (f64, f64) {
+        match self {
+            Direction::North => (1.0, 0.0),
+            Direction::NorthEast => (1.0, 1.0),
+            Direction::East => (0.0, 1.0),
+            Direction::SouthEast => (-1.0, 1.0),
+            Direction::South => (-1.0, 0.0),
+            Direction::SouthWest => (-1.0, -1.0),
+            Direction::West => (0.0, -1.0),
+            Direction::NorthWest => (1.0, -1.0),
+        }
+    }
+}
+
+impl Geohash {
+    // The cell of the given length containing `at`. The precision is clamped
+    // to `1..=MAX_PRECISION`. A NaN or infinite coordinate belongs to no
+    // cell, so it is an error.
+    pub fn encode<P: Into<Coordinates>>(
+        at: P,
+        precision: usize,
+    ) -> Result<Geohash, NonFiniteCoordinates> {
+        let at = at.into();
+        if !at.is_finite() {
+            return Err(NonFiniteCoordinates(at));
+        }
+        let precision = precision.clamp(1, MAX_PRECISION);
+        let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+
+        let mut hash = String::with_capacity(precision);
+        let mut bit = 0;
+        for _ in 0..precision {
+            let mut index = 0;
+            for _ in 0..5 {
+                let (range, value): (&mut (f64, f64), f64) =
+                    if bit % 2 == 0 { (&mut lon, at.lon) } else { (&mut lat, at.lat) };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                bit += 1;
+            }
+            hash.push(ALPHABET[index] as char);
+        }
+        Ok(Geohash(hash))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // The number of characters.
+    pub fn precision(&self) -> usize {
+        self.0.len()
+    }
+
+    // The area the geohash names.
+    pub fn bounds(&self) -> BoundingBox {
+        let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut bit = 0;
+        for byte in self.0.bytes() {
+            let index = ALPHABET.iter().position(|&b| b == byte).unwrap();
+            for shift in (0..5).rev() {
+                let range: &mut (f64, f64) = if bit % 2 == 0 { &mut lon } else { &mut lat };
+                let mid = (range.0 + range.1) / 2.0;
+                if index >> shift & 1 == 1 {
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                bit += 1;
+            }
+        }
+        BoundingBox {
+            min: Coordinates::new(lat.0, lon.0),
+            max: Coordinates::new(lat.1, lon.1),
+        }
+    }
+
+    pub fn center(&self) -> Coordinates {
+        self.bounds().center()
+    }
+
+    pub fn contains<P: Into<Coordinates>>(&self, at: P) -> bool {
+        Geohash::encode(at, self.precision()).is_ok_and(|cell| cell == *self)
+    }
+
+    // The cell one character shorter, or `None` for a single character.
+    pub fn parent(&self) -> Option<Geohash> {
+        let len = self.0.len();
+        (len > 1).then(|| Geohash(self.0[..len - 1].to_string()))
+    }
+
+    // The adjacent cell of the same size. Cells wrap around the
+    // antimeridian, but there is nothing north of the north pole, so the top
+    // and bottom rows have no northern or southern neighbours.
+    pub fn neighbor(&self, direction: Direction) -> Option<Geohash> {
+        let bounds = self.bounds();
+        let center = bounds.center();
+        let (north, east) = direction.offset();
+        let lat = center.lat + north * (bounds.max.lat - bounds.min.lat);
+        if lat.abs() > 90.0 {
+            return None;
+        }
+        let lon = normalize_longitude(center.lon + east * (bounds.max.lon - bounds.min.lon));
+        Geohash::encode(Coordinates::new(lat, lon), self.precision()).ok()
+    }
+
+    // All the neighbours that exist, clockwise from the north.
+    pub fn neighbors(&self) -> Vec<Geohash> {
+        Direction::ALL.iter().filter_map(|&d| self.neighbor(d)).collect()
+    }
+}
+
+impl Display for Geohash {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+// What went wrong while parsing a `Geohash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseGeohashErrorKind {
+    Empty,
+    // A character outside the geohash alphabet.
+    InvalidChar(char),
+    // More than `MAX_PRECISION` characters.
+    TooLong,
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGeohashError {
+    kind: ParseGeohashErrorKind,
+    position: usize,
+}
+
+impl ParseGeohashError {
+    pub fn kind(&self) -> ParseGeohashErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseGeohashError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind {
+            ParseGeohashErrorKind::Empty => return write!(f, "geohash is empty"),
+            ParseGeohashErrorKind::InvalidChar(c) => {
+                write!(f, "`{}` is not a geohash character", c)?
+            }
+            ParseGeohashErrorKind::TooLong => {
+                write!(f, "geohash is longer than {} characters", MAX_PRECISION)?
+            }
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseGeohashError {}
+
+// Geohashes are case-insensitive; they are stored in lowercase.
+impl FromStr for Geohash {
+    type Err = ParseGeohashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |kind, position| Err(ParseGeohashError { kind, position });
+        if s.is_empty() {
+            return error(ParseGeohashErrorKind::Empty, 0);
+        }
+
+        let mut hash = String::with_capacity(s.len());
+        for (position, c) in s.char_indices() {
+            let lower = c.to_ascii_lowercase();
+            if !lower.is_ascii() || !ALPHABET.contains(&(lower as u8)) {
+                return error(ParseGeohashErrorKind::InvalidChar(c), position);
+            }
+            if hash.len() == MAX_PRECISION {
+                return error(ParseGeohashErrorKind::TooLong, position);
+            }
+            hash.push(lower);
+        }
+        Ok(Geohash(hash))
+    }
+}
+
+// Cities bucketed by their geohash cell at a fixed precision. The buckets are
+// kept in geohash order, so every cell sharing a prefix is found with one
+// range scan.
+pub struct GeohashIndex<N = &'static str> {
+    precision: usize,
+    buckets: BTreeMap<Geohash, Vec<City<N>>>,
+}
+
+impl<N> GeohashIndex<N> {
+    pub fn new(precision: usize) -> GeohashIndex<N> {
+        GeohashIndex {
+            precision: precision.clamp(1, MAX_PRECISION),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    // Files `city` under its cell. A city with a NaN or infinite coordinate
+    // has no cell and is refused.
+    pub fn insert(&mut self, city: City<N>) -> Result<(), NonFiniteCoordinates> {
+        let cell = Geohash::encode(&city, self.precision)?;
+        self.buckets.entry(cell).or_default().push(city);
+        Ok(())
+    }
+
+    // Inserts every city, stopping at the first one that is refused. The
+    // error gives its 0-based index; the cities before it stay inserted.
+    pub fn insert_all<I>(&mut self, cities: I) -> Result<(), NonFiniteCity>
+    where
+        I: IntoIterator<Item = City<N>>,
+    {
+        for (index, city) in cities.into_iter().enumerate() {
+            self.insert(city).map_err(|_| NonFiniteCity { index })?;
+        }
+        Ok(())
+    }
+
+    // The occupied cells and their cities, in geohash order.
+    pub fn buckets(&self) -> impl Iterator<Item = (&Geohash, &[City<N>])> + '_ {
+        self.buckets.iter().map(|(cell, cities)| (cell, cities.as_slice()))
+    }
+
+    // Every city inside `cell`, which may be any size. Cells larger than the
+    // buckets cover several of them; smaller ones pick cities out of one.
+    pub fn within<'s>(&'s self, cell: &'s Geohash) -> impl Iterator<Item = &'s City<N>> + 's {
+        let prefix = &cell.0[..cell.precision().min(self.precision)];
+        self.buckets
+            .range(Geohash(prefix.to_string())..)
+            .take_while(move |(key, _)| key.0.starts_with(prefix))
+            .flat_map(|(_, cities)| cities)
+            .filter(move |city| cell.precision() <= self.precision || cell.contains(*city))
+    }
+
+    // The cities in the bucket holding `at` and the eight around it: a quick
+    // set of candidates for "what is near here", though not a sorted or
+    // exact one.
+    pub fn near<P: Into<Coordinates>>(
+        &self,
+        at: P,
+    ) -> Result<impl Iterator<Item = &City<N>> + '_, NonFiniteCoordinates> {
+        let cell = Geohash::encode(at, self.precision)?;
+        let mut cells = cell.neighbors();
+        cells.push(cell);
+        cells.sort();
+        cells.dedup();
+        Ok(cells
+            .into_iter()
+            .filter_map(|cell| self.buckets.get(&cell))
+            .flatten())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Geohash, GeohashIndex};
+    use crate::city::{City, NonFiniteCity};
+    use crate::geo::Coordinates;
+
+    #[test]
+    fn non_finite_coordinates_have_no_cell() {
+        for (lat, lon) in [(f64::NAN, 0.0), (0.0, f64::NAN), (f64::INFINITY, 0.0)] {
+            assert!(Geohash::encode(Coordinates::new(lat, lon), 5).is_err());
+        }
+        let cell = Geohash::encode(Coordinates::new(0.0, 0.0), 5).unwrap();
+        assert!(!cell.contains(Coordinates::new(f64::NAN, 0.0)));
+
+        let dublin = City { name: "Dublin", lat: 53.35, lon: -6.26 };
+        let lost = City { name: "Lost", lat: f32::NAN, lon: 0.0 };
+        let mut index = GeohashIndex::new(5);
+        assert!(index.insert(lost).is_err());
+        assert_eq!(index.insert_all([dublin, lost]), Err(NonFiniteCity { index: 1 }));
+        assert_eq!(index.buckets().count(), 1);
+        assert!(index.near(Coordinates::new(f64::NAN, 0.0)).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::city::{check_finite, City, NonFiniteCity, OwnedCity};
+use crate::json::{self, ParseJsonError, Value};
+
+// A `Display` adapter that writes cities as a GeoJSON `FeatureCollection`
+// of `Point` features, one feature per line, each with the city's name as its
+// `name` property. Note that GeoJSON puts the longitude first.
+pub struct GeoJson<'a, N>(&'a [City<N>]);
+
+impl<'a, N> GeoJson<'a, N> {
+    // JSON has no way to write NaN or infinity, so every city's position is
+    // checked up front.
+    pub fn new(cities: &'a [City<N>]) -> Result<GeoJson<'a, N>, NonFiniteCity> {
+        check_finite(cities)?;
+        Ok(GeoJson(cities))
+    }
+}
+
+impl<N: Display> Display for GeoJson<'_, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{{\"type\":\"FeatureCollection\",\"features\":[")?;
+        for (i, city) in self.0.iter().enumerate() {
+            f.write_str("{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",")?;
+            write!(f, "\"coordinates\":[{},{}]}},", city.lon, city.lat)?;
+            f.write_str("\"properties\":{\"name\":")?;
+            json::write_string(f, &city.name.to_string())?;
+            f.write_str("}}")?;
+            writeln!(f, "{}", if i + 1 < self.0.len() { "," } else { "" })?;
+        }
+        writeln!(f, "]}}")
+    }
+}
+
+// Why a feature could not be read as a city.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureErrorKind {
+    // Not an object with `"type": "Feature"`.
+    NotAFeature,
+    // The geometry is missing or is not a `Point`.
+    NotAPoint,
+    // The point's `coordinates` are not an array of at least two numbers.
+    InvalidCoordinates,
+    // A latitude or longitude outside the globe.
+    OutOfRange,
+    // There is no string `name` property.
+    MissingName,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadGeoJsonError {
+    // The text is not valid JSON.
+    Syntax(ParseJsonError),
+    // The top level is not a `FeatureCollection` with a `features` array.
+    NotAFeatureCollection,
+    // A feature, by 0-based index, that could not be turned into a city.
+    Feature { index: usize, kind: FeatureErrorKind },
+}
+
+impl Display for ReadGeoJsonError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReadGeoJsonError::Syntax(e) => write!(f, "invalid JSON: {}", e),
+            ReadGeoJsonError::NotAFeatureCollection => write!(f, "expected a FeatureCollection"),
+            ReadGeoJsonError::Feature { index, kind } => {
+                write!(f, "feature {}: ", index)?;
+                match kind {
+                    FeatureErrorKind::NotAFeature => write!(f, "not a Feature"),
+                    FeatureErrorKind::NotAPoint => write!(f, "geometry is not a Point"),
+                    FeatureErrorKind::InvalidCoordinates => write!(f, "invalid coordinates"),
+                    FeatureErrorKind::OutOfRange => write!(f, "coordinates out of range"),
+                    FeatureErrorKind::MissingName => write!(f, "no name property"),
+                }
+            }
+        }
+    }
+}
+
+impl Error for ReadGeoJsonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadGeoJsonError::Syntax(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseJsonError> for ReadGeoJsonError {
+    fn from(e: ParseJsonError) -> Self {
+        ReadGeoJsonError::Syntax(e)
+    }
+}
+
+// Reads cities back from a GeoJSON `FeatureCollection`, such as `GeoJson`
+// writes. Every feature must be a `Point` with a `name` property; other
+// properties and any altitude are ignored.
+pub fn read_geojson(text: &str) -> Result<Vec<OwnedCity>, ReadGeoJsonError> {
+    let root = json::parse(text)?;
+    let features = match (root.get("type").and_then(Value::as_str), root.get("features")) {
+        (Some("FeatureCollection"), Some(Value::Array(features))) => features,
+        _ => return Err(ReadGeoJsonError::NotAFeatureCollection),
+    };
+
+    features
+        .iter()
+        .enumerate()
+        .map(|(index, feature)| {
+            read_feature(feature).map_err(|kind| ReadGeoJsonError::Feature { index, kind })
+        })
+        .collect()
+}
+
+fn read_feature(feature: &Value) -> Result<OwnedCity, FeatureErrorKind> {
+    if feature.get("type").and_then(Value::as_str) != Some("Feature") {
+        return Err(FeatureErrorKind::NotAFeature);
+    }
+    let geometry = feature.get("geometry").ok_or(FeatureErrorKind::NotAPoint)?;
+    if geometry.get("type").and_then(Value::as_str) != Some("Point") {
+        return Err(FeatureErrorKind::NotAPoint);
+    }
+
+    let position = geometry
+        .get("coordinates")
+        .and_then(Value::as_array)
+        .ok_or(FeatureErrorKind::InvalidCoordinates)?;
+    let (lon, lat) = match position {
+        [lon, lat, ..] => (lon.as_f64(), lat.as_f64()),
+        _ => (None, None),
+    };
+    let (Some(lon), Some(lat)) = (lon, lat) else {
+        return Err(FeatureErrorKind::InvalidCoordinates);
+    };
+    if lat.abs() > 90.0 || lon.abs() > 180.0 {
+        return Err(FeatureErrorKind::OutOfRange);
+    }
+
+    let name = feature
+        .get("properties")
+        .and_then(|p| p.get("name"))
+        .and_then(Value::as_str)
+        .ok_or(FeatureErrorKind::MissingName)?;
+
+    Ok(City {
+        name: name.to_string(),
+        lat: lat as f32,
+        lon: lon as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoJson;
+    use crate::city::{City, NonFiniteCity};
+
+    #[test]
+    fn non_finite_coordinates_are_not_written() {
+        let dublin = City { name: "Dublin", lat: 53.35, lon: -6.26 };
+        for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let cities = [dublin, City { lat: bad, ..dublin }];
+            assert_eq!(GeoJson::new(&cities).err(), Some(NonFiniteCity { index: 1 }));
+            let cities = [City { lon: bad, ..dublin }];
+            assert_eq!(GeoJson::new(&cities).err(), Some(NonFiniteCity { index: 0 }));
+        }
+        assert!(GeoJson::new(&[dublin]).is_ok());
+    }
+}
@@ -0,0 +1,361 @@
+// Just enough JSON to read GeoJSON back in: a parser into a `Value` tree and
+// the string escaping the writers need.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    // Members in document order. Lookups take the first match.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+// What went wrong while parsing JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseJsonErrorKind {
+    // A character that does not fit the grammar at this point.
+    UnexpectedChar(char),
+    // The input stopped in the middle of a value.
+    UnexpectedEnd,
+    // Something that started like a number could not be read as one.
+    InvalidNumber,
+    // A backslash escape that JSON does not define, or a bad `\u` sequence.
+    InvalidEscape,
+    // Arrays and objects nested too deeply to parse without risking the
+    // stack.
+    TooDeep,
+}
+
+// A parse failure along with the byte offset into the input where it was
+// detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseJsonError {
+    kind: ParseJsonErrorKind,
+    position: usize,
+}
+
+impl ParseJsonError {
+    pub fn kind(&self) -> ParseJsonErrorKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseJsonError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind {
+            ParseJsonErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c)?,
+            ParseJsonErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseJsonErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ParseJsonErrorKind::InvalidEscape => write!(f, "invalid escape sequence")?,
+            ParseJsonErrorKind::TooDeep => write!(f, "too deeply nested")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseJsonError {}
+
+const MAX_DEPTH: usize = 128;
+
+pub fn parse(src: &str) -> Result<Value, ParseJsonError> {
+    let mut parser = Parser { src, pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(_) => Err(parser.unexpected()),
+    }
+}
+
+// Writes `s` as a JSON string literal, quotes included.
+pub fn write_string(f: &mut Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+// A hand-rolled cursor over the input. Positions are byte offsets so they can
+// be used to slice the original string.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    // JSON whitespace is exactly these four characters.
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn error(&self, kind: ParseJsonErrorKind) -> ParseJsonError {
+        ParseJsonError { kind, position: self.pos }
+    }
+
+    fn unexpected(&self) -> ParseJsonError {
+        match self.peek() {
+            Some(c) => self.error(ParseJsonErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseJsonErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseJsonError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ParseJsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error(ParseJsonErrorKind::TooDeep));
+        }
+        self.skip_whitespace();
+        for (word, value) in [
+            ("null", Value::Null),
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+        ] {
+            if self.rest().starts_with(word) {
+                self.pos += word.len();
+                return Ok(value);
+            }
+        }
+
+        match self.peek() {
+            Some('"') => self.string().map(Value::String),
+            Some('[') => self.array(depth),
+            Some('{') => self.object(depth),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    // Both containers are a delimited, comma-separated list; `item` reads
+    // one entry.
+    fn list(
+        &mut self,
+        open: char,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<(), ParseJsonError>,
+    ) -> Result<(), ParseJsonError> {
+        self.expect(open)?;
+        self.skip_whitespace();
+        if self.eat(close) {
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            self.skip_whitespace();
+            if self.eat(close) {
+                return Ok(());
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, ParseJsonError> {
+        let mut items = Vec::new();
+        self.list('[', ']', |p| {
+            items.push(p.value(depth + 1)?);
+            Ok(())
+        })?;
+        Ok(Value::Array(items))
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, ParseJsonError> {
+        let mut members = Vec::new();
+        self.list('{', '}', |p| {
+            p.skip_whitespace();
+            if p.peek() != Some('"') {
+                return Err(p.unexpected());
+            }
+            let key = p.string()?;
+            p.skip_whitespace();
+            p.expect(':')?;
+            members.push((key, p.value(depth + 1)?));
+            Ok(())
+        })?;
+        Ok(Value::Object(members))
+    }
+
+    fn string(&mut self) -> Result<String, ParseJsonError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.unexpected());
+            };
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                '\\' => {
+                    self.pos += 1;
+                    s.push(self.escape()?);
+                }
+                c if c.is_control() => return Err(self.unexpected()),
+                c => {
+                    self.pos += c.len_utf8();
+                    s.push(c);
+                }
+            }
+        }
+    }
+
+    // The part of an escape after the backslash.
+    fn escape(&mut self) -> Result<char, ParseJsonError> {
+        let start = self.pos - 1;
+        let invalid = ParseJsonError {
+            kind: ParseJsonErrorKind::InvalidEscape,
+            position: start,
+        };
+        let Some(c) = self.peek() else {
+            return Err(self.unexpected());
+        };
+        self.pos += c.len_utf8();
+        Ok(match c {
+            '"' | '\\' | '/' => c,
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = self.hex4().ok_or(invalid.clone())?;
+                // Characters outside the Basic Multilingual Plane come as a
+                // UTF-16 surrogate pair, as in `\ud83d\ude00`.
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if !self.rest().starts_with("\\u") {
+                        return Err(invalid);
+                    }
+                    self.pos += 2;
+                    let low = self.hex4().ok_or(invalid.clone())?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(invalid);
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                char::from_u32(code).ok_or(invalid)?
+            }
+            _ => return Err(invalid),
+        })
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.rest().get(..4)?;
+        let code = u32::from_str_radix(digits, 16).ok()?;
+        // `from_str_radix` would also take a leading `+`.
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        Some(code)
+    }
+
+    // `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+    fn number(&mut self) -> Result<Value, ParseJsonError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let rest = p.rest();
+            let n = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            p.pos += n;
+            n
+        };
+        let invalid = ParseJsonError {
+            kind: ParseJsonErrorKind::InvalidNumber,
+            position: start,
+        };
+
+        self.eat('-');
+        let int_start = self.pos;
+        let int_digits = digits(self);
+        if int_digits == 0 || (int_digits > 1 && self.src[int_start..].starts_with('0')) {
+            return Err(invalid);
+        }
+        if self.eat('.') && digits(self) == 0 {
+            return Err(invalid);
+        }
+        if self.eat('e') || self.eat('E') {
+            let _ = self.eat('+') || self.eat('-');
+            if digits(self) == 0 {
+                return Err(invalid);
+            }
+        }
+
+        self.src[start..self.pos]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| invalid)
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::city::{check_finite, City, NonFiniteCity};
+
+// A `Display` adapter that writes cities as a KML document with one
+// `Placemark` per city. Like GeoJSON, KML puts the longitude first.
+pub struct Kml<'a, N>(&'a [City<N>]);
+
+impl<'a, N> Kml<'a, N> {
+    // As with `GeoJson::new`, every city's position is checked up front.
+    pub fn new(cities: &'a [City<N>]) -> Result<Kml<'a, N>, NonFiniteCity> {
+        check_finite(cities)?;
+        Ok(Kml(cities))
+    }
+}
+
+impl<N: Display> Display for Kml<'_, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(f, "<kml xmlns=\"http://www.opengis.net/kml/2.2\">")?;
+        writeln!(f, "  <Document>")?;
+        for city in self.0 {
+            writeln!(f, "    <Placemark>")?;
+            writeln!(f, "      <name>{}</name>", Escaped(&city.name.to_string()))?;
+            writeln!(
+                f,
+                "      <Point><coordinates>{},{}</coordinates></Point>",
+                city.lon, city.lat
+            )?;
+            writeln!(f, "    </Placemark>")?;
+        }
+        writeln!(f, "  </Document>")?;
+        writeln!(f, "</kml>")
+    }
+}
+
+// Text with the characters that are special in XML replaced by entities. The
+// SVG map uses it too.
+pub(crate) struct Escaped<'a>(pub(crate) &'a str);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kml;
+    use crate::city::{City, NonFiniteCity};
+
+    #[test]
+    fn non_finite_coordinates_are_not_written() {
+        let dublin = City { name: "Dublin", lat: 53.35, lon: -6.26 };
+        for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let cities = [dublin, City { lat: bad, ..dublin }];
+            assert_eq!(Kml::new(&cities).err(), Some(NonFiniteCity { index: 1 }));
+            let cities = [City { lon: bad, ..dublin }];
+            assert_eq!(Kml::new(&cities).err(), Some(NonFiniteCity { index: 0 }));
+        }
+        let kml = Kml::new(&[dublin]).unwrap().to_string();
+        assert!(kml.contains("<coordinates>-6.26,53.35</coordinates>"));
+    }
+}
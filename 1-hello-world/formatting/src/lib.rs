@@ -0,0 +1,18 @@
+// The types from this exercise live in a library so that other exercises can
+// build on them; `main.rs` only prints them.
+pub mod city;
+pub mod color;
+pub mod colorspace;
+pub mod cvd;
+pub mod gazetteer;
+pub mod geo;
+pub mod geohash;
+pub mod geojson;
+pub mod json;
+pub mod kml;
+pub mod map;
+pub mod nearest;
+pub mod ppm;
+pub mod solar;
+pub mod table;
+pub mod terminal;
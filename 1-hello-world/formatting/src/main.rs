@@ -1,59 +1,425 @@
-use std::fmt::{self, Formatter, Display};
-
-struct City {
-    name: &'static str,
-    // Latitude
-    lat: f32,
-    // Longitude
-    lon: f32,
-}
+use std::{env, fs};
+
+use formatting::city::{City, OwnedCity};
+use formatting::color::{Color, ColorForm, ParseColorErrorKind, Rgba, TextSize, WcagLevel};
+use formatting::colorspace::{gradient, Lab};
+use formatting::cvd::{simulate_ppm, Deficiency};
+use formatting::gazetteer::read_gazetteer;
+use formatting::geo::{
+    normalize_longitude, BoundingBox, Coordinates, Distance, DistanceUnit,
+    ParseCoordinatesErrorKind,
+};
+use formatting::geohash::{Geohash, GeohashIndex};
+use formatting::geojson::{read_geojson, GeoJson};
+use formatting::kml::Kml;
+use formatting::map::{AsciiMap, Projection, SvgMap, MERCATOR_MAX_LAT};
+use formatting::nearest::CityIndex;
+use formatting::ppm::Image;
+use formatting::solar::{Date, Daylight};
+use formatting::table::{display_width, Align, Column, Table, TableFormat};
+use formatting::terminal::{xterm_color, ColorSupport};
+
+fn main() {
+    // The coordinates are kept as given, even where that is more precision
+    // than an `f32` holds.
+    #[allow(clippy::excessive_precision)]
+    let cities = [
+        City { name: "Dublin", lat: 53.347778, lon: -6.259722 },
+        City { name: "Oslo", lat: 59.95, lon: 10.75 },
+        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
+    ];
+    // Print the cities as a table. Each column pulls one value out of a row.
+    let table = Table::new(vec![
+        Column::new("City", |c: &City| c.name),
+        Column::new("Latitude", |c: &City| c.lat).align(Align::Right).precision(3),
+        Column::new("Longitude", |c: &City| c.lon).align(Align::Right).precision(3),
+        Column::new("Position", |c: &City| c.to_string()),
+    ]);
+    println!("{}", table.display(&cities, TableFormat::Boxed));
+    println!("{}", table.display(&cities, TableFormat::Markdown));
+    print!("{}", table.render(&cities, TableFormat::Csv));
 
-impl Display for City {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
-        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+    // Distances and bearings between cities. The haversine formula treats
+    // the Earth as a sphere; Vincenty's formula uses the WGS-84 ellipsoid and
+    // agrees with published geodesic distances to well under a metre.
+    let [dublin, oslo, vancouver] = cities;
+    let great_circle = dublin.distance_to(oslo);
+    let ellipsoidal = dublin.vincenty_distance_to(oslo).unwrap();
+    println!(
+        "{} to {}: {:.1} ({:.1}), or {:.1} on the ellipsoid, setting off on {:.1}°",
+        dublin.name,
+        oslo.name,
+        great_circle,
+        great_circle.display(DistanceUnit::Miles),
+        ellipsoidal,
+        dublin.initial_bearing_to(oslo)
+    );
 
-        write!(f, "{}: {:.3}°{} {:.3}°{}",
-               self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+    // Heading out along the initial bearing for the full distance arrives at
+    // the destination, passing the midpoint halfway.
+    let bearing = dublin.initial_bearing_to(vancouver);
+    let distance = dublin.distance_to(vancouver);
+    let arrival = dublin.destination(bearing, distance);
+    assert!(arrival.distance_to(vancouver.coordinates()).meters() < 1.0);
+    let halfway = dublin.destination(bearing, Distance::from_meters(distance.meters() / 2.0));
+    assert!(halfway.distance_to(dublin.midpoint(vancouver)).meters() < 1.0);
+    println!(
+        "Halfway from {} to {} is {:.3}",
+        dublin.name, vancouver.name, halfway
+    );
+
+    // Coordinates read back from what `City` prints, in either form, and from
+    // plain signed degrees.
+    println!("{:#}", dublin);
+    for city in cities {
+        for text in [city.to_string(), format!("{:#}", city)] {
+            let (_, position) = text.split_once(": ").unwrap();
+            let parsed: Coordinates = position.parse().unwrap();
+            assert!(parsed.distance_to(city.coordinates()).meters() < 60.0);
+        }
     }
-}
+    let plain: Coordinates = "53.347778, -6.259722".parse().unwrap();
+    let dms: Coordinates = "6°15′35″W, 53°20′52″N".parse().unwrap();
+    assert!(plain.distance_to(dms).meters() < 30.0);
 
-#[derive(Debug)]
-struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
-}
+    // Out-of-range and malformed input is rejected with the reason and where.
+    let error = "91, 0".parse::<Coordinates>().unwrap_err();
+    assert_eq!(error.kind(), ParseCoordinatesErrorKind::LatitudeOutOfRange);
+    assert_eq!(error.position(), 0);
+    let error = "53°60'N 6°W".parse::<Coordinates>().unwrap_err();
+    assert_eq!(error.kind(), ParseCoordinatesErrorKind::InvalidMinutesOrSeconds);
+    println!("Error: {}", "-53°N 6°W".parse::<Coordinates>().unwrap_err());
+
+    // A gazetteer is usually a file passed to `load_gazetteer`; any reader
+    // will do. The header picks out the columns, in any order.
+    let csv = "\
+country,name,latitude,longitude
+IE,Dublin,53.347778,-6.259722
+IE,Cork,51.8985,-8.4756
+GB,London,51.5072,-0.1276
+GB,Edinburgh,55.9533,-3.1883
+NO,Oslo,59.95,10.75
+US,\"Washington, D.C.\",38.9072,-77.0369
+CA,Vancouver,49.25,-123.1
+";
+    let gazetteer: Vec<OwnedCity> = read_gazetteer(csv.as_bytes()).unwrap();
+    assert_eq!(gazetteer[5].name, "Washington, D.C.");
+    let index: CityIndex<String> = gazetteer.into_iter().collect();
 
-impl Display for Color {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(
-            f,
-            "RGB ({red}, {green}, {blue}) 0x{red:0>2X}{green:0>2X}{blue:0>2X}",
-            red = self.red,
-            green = self.green,
-            blue = self.blue
-        )
+    let nearest = index.nearest(dublin, 3);
+    let names: Vec<&str> = nearest.iter().map(|(city, _)| city.name.as_str()).collect();
+    assert_eq!(names, ["Dublin", "Cork", "Edinburgh"]);
+    for (city, distance) in index.within(dublin, Distance::from_km(500.0)) {
+        println!("{} is {:.1} away", city, distance);
     }
-}
 
-fn main() {
-    for city in [
-        City { name: "Dublin", lat: 53.347778, lon: -6.259722 },
-        City { name: "Oslo", lat: 59.95, lon: 10.75 },
-        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
-    ].iter() {
-        println!("{}", *city);
+    let error = read_gazetteer("name\tlat\tlon\nNowhere\t95\t0\n".as_bytes()).unwrap_err();
+    println!("Error: {}", error);
+
+    // On a larger gazetteer the tree gives the same answers as comparing
+    // against every city, without doing so.
+    let n = 100_000;
+    let golden_angle = 180.0 * (3.0 - 5f64.sqrt());
+    let points: CityIndex<usize> = (0..n)
+        .map(|i| City {
+            name: i,
+            lat: (1.0 - 2.0 * (i as f64 + 0.5) / n as f64).asin().to_degrees() as f32,
+            lon: normalize_longitude(i as f64 * golden_angle) as f32,
+        })
+        .collect();
+    for city in cities {
+        let found: Vec<usize> = points.nearest(city, 5).iter().map(|(p, _)| p.name).collect();
+        let mut scan: Vec<(f64, usize)> = points
+            .cities()
+            .map(|p| (city.distance_to(p).meters(), p.name))
+            .collect();
+        scan.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let expected: Vec<usize> = scan[..5].iter().map(|&(_, name)| name).collect();
+        assert_eq!(found, expected);
+
+        let within = points.within(city, Distance::from_km(100.0));
+        assert_eq!(within.len(), scan.iter().take_while(|p| p.0 <= 100_000.0).count());
+    }
+
+    // Cities go out as GeoJSON and KML for mapping tools, and GeoJSON comes
+    // back in.
+    let geojson = GeoJson::new(&cities).unwrap().to_string();
+    let kml = Kml::new(&cities).unwrap().to_string();
+    print!("{}{}", geojson, kml);
+    let dir = env::temp_dir();
+    fs::write(dir.join("cities.geojson"), &geojson).unwrap();
+    fs::write(dir.join("cities.kml"), &kml).unwrap();
+    println!("Wrote cities.geojson and cities.kml to {}", dir.display());
+
+    let read_back = read_geojson(&geojson).unwrap();
+    for (original, copy) in cities.iter().zip(&read_back) {
+        assert_eq!(copy.name, original.name);
+        assert_eq!((copy.lat, copy.lon), (original.lat, original.lon));
+    }
+    let error = read_geojson(r#"{"type": "FeatureCollection", "features": [{"type": "Feature",
+        "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}}]}"#);
+    println!("Error: {}", error.unwrap_err());
+    let adrift = [City { name: "Adrift", lat: f32::NAN, lon: 0.0 }];
+    if let Err(e) = GeoJson::new(&adrift) {
+        println!("Error: {}", e);
+    }
+
+    // Geohashes name nested cells of the globe; a longer hash is a smaller
+    // cell inside the one named by its prefix.
+    let jutland = Geohash::encode(Coordinates::new(57.64911, 10.40744), 11).unwrap();
+    assert_eq!(jutland.as_str(), "u4pruydqqvj");
+    assert!(jutland.bounds().contains(Coordinates::new(57.64911, 10.40744)));
+    let cell: Geohash = "GBSUV".parse().unwrap();
+    let around: Vec<String> = cell.neighbors().iter().map(|n| n.to_string()).collect();
+    assert_eq!(around, ["gbsvj", "gbsvn", "gbsuy", "gbsuw", "gbsut", "gbsus", "gbsuu", "gbsvh"]);
+    let arctic = Geohash::encode(Coordinates::new(89.99, 0.0), 3).unwrap();
+    assert_eq!(arctic.neighbors().len(), 5);
+    println!("Error: {}", "u4pa".parse::<Geohash>().unwrap_err());
+
+    // Bucketed by five-character cells (about 5 km across), cities can be
+    // looked up by any prefix or by the cells around a point.
+    let mut buckets = GeohashIndex::new(5);
+    buckets.insert_all(read_gazetteer(csv.as_bytes()).unwrap()).unwrap();
+    for (cell, cities) in buckets.buckets() {
+        let names: Vec<&str> = cities.iter().map(|c| c.name.as_str()).collect();
+        println!("{} around {:.3}: {:?}", cell, cell.center(), names);
+    }
+    let british_isles: Geohash = "gc".parse().unwrap();
+    assert_eq!(buckets.within(&british_isles).count(), 4);
+    let near: Vec<&str> = buckets.near(dublin).unwrap().map(|c| c.name.as_str()).collect();
+    assert_eq!(near, ["Dublin"]);
+
+    // The sun over each city at the June solstice, in UTC. Dublin's sunrise
+    // and sunset are 04:57 and 21:57 Irish summer time.
+    let solstice = Date::new(2024, 6, 21).unwrap();
+    for city in cities {
+        let day = city.solar_day(solstice).unwrap();
+        let length = day.day_length().as_secs() / 60;
+        println!(
+            "{} on {}: sun {}, noon {}, civil twilight {}, {}h{:02}m of daylight",
+            city.name,
+            solstice,
+            day.sun,
+            day.solar_noon,
+            day.civil_twilight,
+            length / 60,
+            length % 60
+        );
     }
+    let Daylight::Normal { rise, set } = dublin.solar_day(solstice).unwrap().sun else {
+        unreachable!()
+    };
+    assert!((rise.minutes() - (3.0 * 60.0 + 57.0)).abs() < 2.0);
+    assert!((set.minutes() - (20.0 * 60.0 + 57.0)).abs() < 2.0);
 
-    for color in [
+    // Inside the Arctic Circle the sun neither sets at midsummer nor rises at
+    // midwinter.
+    let tromso = City { name: "Tromsø", lat: 69.6496, lon: 18.956 };
+    let midwinter = Date::new(2024, 12, 21).unwrap();
+    assert_eq!(tromso.solar_day(solstice).unwrap().sun, Daylight::PolarDay);
+    let winter = tromso.solar_day(midwinter).unwrap();
+    assert_eq!(winter.sun, Daylight::PolarNight);
+    println!(
+        "{} on {}: {}, civil twilight {}",
+        tromso.name, midwinter, winter.sun, winter.civil_twilight
+    );
+    println!("Error: {}", Date::new(2023, 2, 29).unwrap_err());
+    let nowhere = Coordinates::new(f64::NAN, 0.0);
+    println!("Error: {}", nowhere.solar_day(solstice).unwrap_err());
+
+    // Maps for a quick look at where the cities are: a character grid in the
+    // terminal, and an SVG for anything more.
+    assert_eq!(Projection::Equirectangular.project(Coordinates::new(0.0, 0.0)), (0.5, 0.5));
+    let (_, top) = Projection::WebMercator.project(Coordinates::new(MERCATOR_MAX_LAT, 0.0));
+    assert!(top.abs() < 1e-12);
+    let places: Vec<OwnedCity> = read_gazetteer(csv.as_bytes()).unwrap();
+    print!("{}", AsciiMap::new(72, 18).render(&places));
+    let europe = BoundingBox {
+        min: Coordinates::new(35.0, -15.0),
+        max: Coordinates::new(65.0, 30.0),
+    };
+    let zoomed = AsciiMap::new(48, 16).projection(Projection::WebMercator).bounds(europe);
+    print!("{}", zoomed.render(&places));
+    let svg = dir.join("cities.svg");
+    SvgMap::new(960.0, 960.0).projection(Projection::WebMercator).save(&places, &svg).unwrap();
+    println!("Wrote {}", svg.display());
+
+    // Widths are measured in terminal columns, not bytes or `char`s.
+    assert_eq!(display_width("Dublin"), 6);
+    assert_eq!(display_width("東京"), 4);
+    assert_eq!(display_width("Zu\u{308}rich"), 6);
+
+    let colors = [
         Color { red: 128, green: 255, blue: 90 },
         Color { red: 0, green: 3, blue: 254 },
         Color { red: 0, green: 0, blue: 0 },
-    ].iter() {
+    ];
+    for color in colors.iter() {
         // Switch this to use {} once you've added an implementation
         // for fmt::Display.
         // println!("{:?}", *color);
-        println!("{}", color)
+        // A swatch in the colour itself goes in front, if the terminal can
+        // show one.
+        println!("{} {}", color.swatch(), color)
+    }
+
+    // `NO_COLOR` beats everything; otherwise `COLORTERM` and `TERM` decide.
+    assert_eq!(ColorSupport::from_env(true, Some("truecolor"), None), ColorSupport::None);
+    assert_eq!(
+        ColorSupport::from_env(false, Some("truecolor"), Some("xterm")),
+        ColorSupport::TrueColor
+    );
+    assert_eq!(
+        ColorSupport::from_env(false, None, Some("xterm-256color")),
+        ColorSupport::Ansi256
+    );
+    assert_eq!(ColorSupport::from_env(false, None, Some("xterm")), ColorSupport::Ansi16);
+    assert_eq!(ColorSupport::from_env(false, None, Some("dumb")), ColorSupport::None);
+
+    // Without truecolor, each colour falls back to the nearest palette entry.
+    let swatch = colors[0].swatch().width(1);
+    let truecolor = swatch.support(ColorSupport::TrueColor);
+    assert_eq!(truecolor.to_string(), "\x1b[48;2;128;255;90m \x1b[0m");
+    assert_eq!(swatch.support(ColorSupport::Ansi256).to_string(), "\x1b[48;5;119m \x1b[0m");
+    assert_eq!(swatch.support(ColorSupport::Ansi16).to_string(), "\x1b[102m \x1b[0m");
+    assert_eq!(swatch.support(ColorSupport::None).to_string(), " ");
+    assert_eq!(xterm_color(119), Color { red: 135, green: 255, blue: 95 });
+    for index in 16..=255 {
+        assert_eq!(xterm_color(xterm_color(index).to_ansi256()), xterm_color(index));
+    }
+    let label = colors[2].paint("black").support(ColorSupport::Ansi16);
+    assert_eq!(format!("{:>6}", label), "\x1b[30m black\x1b[0m");
+
+    let table = Table::new(vec![
+        Column::new("Red", |c: &Color| c.red).align(Align::Right),
+        Column::new("Green", |c: &Color| c.green).align(Align::Right),
+        Column::new("Blue", |c: &Color| c.blue).align(Align::Right),
+        Column::new("Hex", |c: &Color| format!("#{:02X}{:02X}{:02X}", c.red, c.green, c.blue)),
+    ]);
+    print!("{}", table.display(&colors, TableFormat::Boxed));
+
+    // Colours parse from hex, CSS functions and CSS names; each of these is
+    // the first colour above.
+    for text in ["#80FF5A", "0x80ff5a", "rgb(128, 255, 90)", "RGB(50.2%, 100%, 35.3%)"] {
+        assert_eq!(text.parse::<Color>().unwrap(), colors[0], "{}", text);
+    }
+    assert_eq!("#8F5".parse::<Color>().unwrap(), Color { red: 0x88, green: 0xFF, blue: 0x55 });
+    assert_eq!("RebeccaPurple".parse::<Color>().unwrap(), Color { red: 102, green: 51, blue: 153 });
+
+    // `Rgba` takes an alpha channel too, and blends over a background.
+    let veil: Rgba = "rgba(0, 3, 254, 50%)".parse().unwrap();
+    assert_eq!(veil, "#0003FE80".parse().unwrap());
+    println!("{} over white is {}", veil, veil.over("white".parse().unwrap()));
+
+    // Errors say what went wrong and which notation was expected.
+    for text in ["#80FG5A", "#80FF5", "rgb(50%, 255, 90)", "rgb(0, 3, 254, 0.5)", "grey50"] {
+        println!("Error: {}", text.parse::<Color>().unwrap_err());
+    }
+    let error = "rgb(300, 0, 0)".parse::<Color>().unwrap_err();
+    assert_eq!(error.kind(), ParseColorErrorKind::OutOfRange);
+    assert_eq!((error.form(), error.position()), (Some(ColorForm::Function), 4));
+
+    // Perceptual colour spaces. Every `Color` survives a trip through CIELAB
+    // or OKLab unchanged.
+    let red: Color = "red".parse().unwrap();
+    let lab = red.to_lab();
+    let oklab = red.to_oklab();
+    println!(
+        "{} is L*a*b* {:.2} {:.2} {:.2}, OKLab {:.4} {:.4} {:.4}",
+        red, lab.l, lab.a, lab.b, oklab.l, oklab.a, oklab.b
+    );
+    assert!((lab.l - 53.24).abs() < 0.01 && (lab.a - 80.09).abs() < 0.01);
+    assert!((oklab.l - 0.62796).abs() < 1e-4 && (oklab.a - 0.22486).abs() < 1e-4);
+    for color in colors {
+        assert_eq!(Color::from_lab(color.to_lab()), color);
+        assert_eq!(Color::from_oklab(color.to_oklab()), color);
+    }
+
+    // CIEDE2000 against pairs from Sharma, Wu and Dalal's test data.
+    let pairs = [
+        ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+        ((50.0, -1.3802, -84.2814), (50.0, 0.0, -82.7485), 1.0000),
+        ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+        ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+    ];
+    for ((l1, a1, b1), (l2, a2, b2), expected) in pairs {
+        let (x, y) = (Lab { l: l1, a: a1, b: b1 }, Lab { l: l2, a: a2, b: b2 });
+        assert!((x.delta_e2000(y) - expected).abs() < 1e-4);
+    }
+    let (navy, blue): (Color, Color) = ("navy".parse().unwrap(), "blue".parse().unwrap());
+    let (e76, e2000) = (navy.delta_e76(blue), navy.delta_e2000(blue));
+    println!("Navy to blue: ΔE76 {:.2}, ΔE2000 {:.2}", e76, e2000);
+
+    // Gradients are blended in OKLab and include both ends.
+    let stops = gradient(&[blue, "white".parse().unwrap(), red], 7);
+    assert_eq!((stops[0], stops[3], stops[6]), (blue, "white".parse().unwrap(), red));
+    for stop in &stops {
+        println!("{}", stop);
+    }
+
+    // WCAG contrast. Black on white is the most there is; mid-grey `#777777`
+    // on white is just short of AA for body text.
+    let white: Color = "white".parse().unwrap();
+    let black: Color = "black".parse().unwrap();
+    assert!((black.contrast(white).ratio() - 21.0).abs() < 1e-9);
+    let grey: Color = "#777777".parse().unwrap();
+    let contrast = grey.contrast(white);
+    assert!(!contrast.passes(WcagLevel::AA, TextSize::Normal));
+    assert!(contrast.passes(WcagLevel::AA, TextSize::Large));
+    println!("{} on white: {}", grey, contrast);
+
+    // When a pair fails, the nearest foreground that passes keeps the hue and
+    // moves only as far as it must.
+    for (fg, bg, level) in [
+        (grey, white, WcagLevel::AA),
+        ("#4A90E2".parse().unwrap(), white, WcagLevel::AA),
+        ("#4A90E2".parse().unwrap(), "#1E1E1E".parse().unwrap(), WcagLevel::AAA),
+    ] {
+        let fixed = fg.accessible_foreground(bg, level, TextSize::Normal).unwrap();
+        assert!(fixed.contrast(bg).passes(level, TextSize::Normal));
+        println!("{:?}: {} becomes {}, {}", level, fg, fixed, fixed.contrast(bg));
+    }
+    assert_eq!(white.accessible_foreground(black, WcagLevel::AAA, TextSize::Normal), Some(white));
+
+    // Colour-vision deficiencies. Greys look the same to everyone, severity 0
+    // is normal vision, and red and green move much closer for protanopes.
+    let green: Color = "green".parse().unwrap();
+    for deficiency in Deficiency::ALL {
+        for c in [black, white, grey] {
+            assert_eq!(c.simulate(deficiency, 1.0), c);
+        }
+        assert_eq!(red.simulate(deficiency, 0.0), red);
+        println!(
+            "{:?}: red looks like {}, green like {}",
+            deficiency,
+            red.simulate(deficiency, 1.0),
+            green.simulate(deficiency, 1.0)
+        );
+    }
+    let (protan_red, protan_green) = (
+        red.simulate(Deficiency::Protanopia, 1.0),
+        green.simulate(Deficiency::Protanopia, 1.0),
+    );
+    assert!(protan_red.delta_e2000(protan_green) < red.delta_e2000(green) / 2.0);
+    let half = red.simulate(Deficiency::Protanopia, 0.5).delta_e2000(red);
+    assert!(0.0 < half && half < protan_red.delta_e2000(red));
+
+    // Batch mode over a PPM: a hue sweep across the top, fading to grey below.
+    let (width, height) = (64, 16);
+    let mut image = Image::new(width, height, black);
+    let hues = gradient(&[red, "yellow".parse().unwrap(), green, blue, red], width);
+    for (x, &hue) in hues.iter().enumerate() {
+        for (y, fade) in gradient(&[hue, grey], height).into_iter().enumerate() {
+            image.set_pixel(x, y, fade);
+        }
+    }
+    let sweep = dir.join("formatting-sweep.ppm");
+    image.save_ppm(&sweep).unwrap();
+    assert_eq!(Image::load_ppm(&sweep).unwrap(), image);
+    // Plain-text PPMs with comments and a smaller maximum are read too.
+    let plain = Image::read_ppm("P3 # two pixels\n2 1 15\n15 0 0  0 15 15\n".as_bytes()).unwrap();
+    assert_eq!(plain.pixels, vec![red, "aqua".parse().unwrap()]);
+    for path in simulate_ppm(&sweep, &dir, 1.0).unwrap() {
+        println!("Wrote {}", path.display());
     }
 }
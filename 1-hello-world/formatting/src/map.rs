@@ -0,0 +1,279 @@
+use std::f64::consts::PI;
+use std::fmt::{Display, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::city::City;
+use crate::geo::{BoundingBox, Coordinates};
+use crate::kml::Escaped;
+
+// Web Mercator stops short of the poles, which it would put at infinity; at
+// this latitude the map comes out square.
+pub const MERCATOR_MAX_LAT: f64 = 85.051_128_779_806_59;
+
+// Ways of flattening latitude and longitude onto a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    // Longitude and latitude used directly as x and y ("plate carrée").
+    Equirectangular,
+    // The projection of web map tiles, which keeps shapes but stretches the
+    // high latitudes.
+    WebMercator,
+}
+
+impl Projection {
+    // `at` as a fraction of the whole world's width and height, measured
+    // from the top-left corner at 180°W.
+    pub fn project(self, at: Coordinates) -> (f64, f64) {
+        let x = at.lon / 360.0 + 0.5;
+        let y = match self {
+            Projection::Equirectangular => 0.5 - at.lat / 180.0,
+            Projection::WebMercator => {
+                let phi = at.lat.clamp(-MERCATOR_MAX_LAT, MERCATOR_MAX_LAT).to_radians();
+                0.5 - (PI / 4.0 + phi / 2.0).tan().ln() / (2.0 * PI)
+            }
+        };
+        (x, y)
+    }
+
+    // The part of the world the projection can show.
+    pub fn world(self) -> BoundingBox {
+        let lat = match self {
+            Projection::Equirectangular => 90.0,
+            Projection::WebMercator => MERCATOR_MAX_LAT,
+        };
+        BoundingBox {
+            min: Coordinates::new(-lat, -180.0),
+            max: Coordinates::new(lat, 180.0),
+        }
+    }
+
+    // `at` on a `width` by `height` canvas showing `bounds`, or `None` if it
+    // falls outside them.
+    pub fn to_canvas(
+        self,
+        at: Coordinates,
+        bounds: BoundingBox,
+        width: f64,
+        height: f64,
+    ) -> Option<(f64, f64)> {
+        if !bounds.contains(at) {
+            return None;
+        }
+        let (left, top) = self.project(Coordinates::new(bounds.max.lat, bounds.min.lon));
+        let (right, bottom) = self.project(Coordinates::new(bounds.min.lat, bounds.max.lon));
+        let (x, y) = self.project(at);
+        Some((
+            (x - left) / (right - left) * width,
+            (y - top) / (bottom - top) * height,
+        ))
+    }
+}
+
+// A map drawn with characters, for a look at a dataset without leaving the
+// terminal. Each city is marked with the first letter of its name, or `*`
+// where several share a cell, and listed in a legend underneath. The equator
+// and prime meridian are drawn if they are in view.
+#[derive(Debug, Clone)]
+pub struct AsciiMap {
+    pub projection: Projection,
+    pub bounds: BoundingBox,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl AsciiMap {
+    // An equirectangular map of the whole world. Terminal cells are about
+    // twice as tall as they are wide, so a `2n` by `n` grid looks right.
+    pub fn new(width: usize, height: usize) -> AsciiMap {
+        AsciiMap {
+            projection: Projection::Equirectangular,
+            bounds: Projection::Equirectangular.world(),
+            width,
+            height,
+        }
+    }
+
+    // Switching projection also resets the bounds to what it can show.
+    pub fn projection(self, projection: Projection) -> AsciiMap {
+        AsciiMap {
+            projection,
+            bounds: projection.world(),
+            ..self
+        }
+    }
+
+    pub fn bounds(self, bounds: BoundingBox) -> AsciiMap {
+        AsciiMap { bounds, ..self }
+    }
+
+    pub fn render<N: Display>(&self, cities: &[City<N>]) -> String {
+        let mut grid = vec![vec![' '; self.width]; self.height];
+        // Which cells already hold a city. The mark itself can't tell us, as
+        // an initial may be a digit or punctuation ("6th of October").
+        let mut occupied = vec![vec![false; self.width]; self.height];
+        let (width, height) = (self.width as f64, self.height as f64);
+        let cell = |at: Coordinates| {
+            let (x, y) = self.projection.to_canvas(at, self.bounds, width, height)?;
+            // The right and bottom edges belong to the last column and row.
+            let column = (x as usize).min(self.width.checked_sub(1)?);
+            let row = (y as usize).min(self.height.checked_sub(1)?);
+            Some((row, column))
+        };
+
+        // The graticule goes down first so cities are drawn over it.
+        if let Some((row, _)) = cell(Coordinates::new(0.0, self.bounds.min.lon)) {
+            grid[row].fill('-');
+        }
+        if let Some((_, column)) = cell(Coordinates::new(self.bounds.min.lat, 0.0)) {
+            for row in grid.iter_mut() {
+                row[column] = if row[column] == '-' { '+' } else { '|' };
+            }
+        }
+
+        let mut legend = String::new();
+        for city in cities {
+            let name = city.name.to_string();
+            let initial = name.chars().next().unwrap_or('?').to_uppercase().next().unwrap();
+            let Some((row, column)) = cell(city.coordinates()) else {
+                continue;
+            };
+            let taken = std::mem::replace(&mut occupied[row][column], true);
+            grid[row][column] = if taken { '*' } else { initial };
+            let _ = writeln!(legend, "{} {}", initial, name);
+        }
+
+        let border = format!("+{}+\n", "-".repeat(self.width));
+        let mut out = border.clone();
+        for row in grid {
+            out.push('|');
+            out.extend(row);
+            out.push_str("|\n");
+        }
+        out.push_str(&border);
+        out.push_str(&legend);
+        out
+    }
+}
+
+// A map as an SVG image: a dot and a label for each city over a graticule
+// every 30°.
+#[derive(Debug, Clone)]
+pub struct SvgMap {
+    pub projection: Projection,
+    pub bounds: BoundingBox,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl SvgMap {
+    pub fn new(width: f64, height: f64) -> SvgMap {
+        SvgMap {
+            projection: Projection::Equirectangular,
+            bounds: Projection::Equirectangular.world(),
+            width,
+            height,
+        }
+    }
+
+    // Switching projection also resets the bounds to what it can show.
+    pub fn projection(self, projection: Projection) -> SvgMap {
+        SvgMap {
+            projection,
+            bounds: projection.world(),
+            ..self
+        }
+    }
+
+    pub fn bounds(self, bounds: BoundingBox) -> SvgMap {
+        SvgMap { bounds, ..self }
+    }
+
+    pub fn render<N: Display>(&self, cities: &[City<N>]) -> String {
+        let (w, h) = (self.width, self.height);
+        let at = |lat: f64, lon: f64| {
+            let at = Coordinates::new(lat, lon);
+            self.projection.to_canvas(at, self.bounds, w, h)
+        };
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">"
+        );
+        let _ = writeln!(svg, "  <rect width=\"{w}\" height=\"{h}\" fill=\"#eef4fb\"/>");
+
+        let b = self.bounds;
+        let mut line = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            let _ = writeln!(
+                svg,
+                "  <line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" \
+                 stroke=\"#c8d6e5\"/>"
+            );
+        };
+        for step in -5..=5 {
+            if let Some((_, y)) = at(step as f64 * 30.0, b.min.lon) {
+                line(0.0, y, w, y);
+            }
+        }
+        for step in -6..=6 {
+            if let Some((x, _)) = at(b.min.lat, step as f64 * 30.0) {
+                line(x, 0.0, x, h);
+            }
+        }
+
+        for city in cities {
+            let Some((x, y)) = self.projection.to_canvas(city.coordinates(), b, w, h) else {
+                continue;
+            };
+            let name = city.name.to_string();
+            let _ = writeln!(svg, "  <g>");
+            let _ = writeln!(svg, "    <title>{}</title>", Escaped(&city.to_string()));
+            let _ = writeln!(
+                svg,
+                "    <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"3\" fill=\"#c0392b\"/>"
+            );
+            let _ = writeln!(
+                svg,
+                "    <text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                x + 5.0,
+                y - 5.0,
+                Escaped(&name)
+            );
+            let _ = writeln!(svg, "  </g>");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn save<N: Display, P: AsRef<Path>>(&self, cities: &[City<N>], path: P) -> io::Result<()> {
+        fs::write(path, self.render(cities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsciiMap;
+    use crate::city::City;
+
+    #[test]
+    fn cities_sharing_a_cell_are_starred_whatever_their_initials() {
+        let cities = [
+            City { name: "6th of October", lat: 45.0, lon: 135.0 },
+            City { name: "'s-Hertogenbosch", lat: 45.0, lon: 135.0 },
+            City { name: "6th of October", lat: -45.0, lon: -135.0 },
+        ];
+        let expected = "\
++----+
+|  |*|
+|6-+-|
++----+
+6 6th of October
+' 's-Hertogenbosch
+6 6th of October
+";
+        assert_eq!(AsciiMap::new(4, 2).render(&cities), expected);
+    }
+}
@@ -0,0 +1,202 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+use crate::city::City;
+use crate::geo::{Coordinates, Distance, EARTH_RADIUS_M};
+
+// A k-d tree over cities for nearest-neighbour and radius queries.
+//
+// Each city is placed on the unit sphere as a 3D point. The straight-line
+// (chord) distance between two such points grows with the great-circle
+// distance between them, so an ordinary Euclidean k-d tree in 3D finds the
+// same neighbours as great-circle distance would, with no special cases at
+// the poles or the antimeridian.
+//
+// The tree is implicit: the cities are reordered so that the median of every
+// slice is its root, split alternately on x, y and z.
+pub struct CityIndex<N = &'static str> {
+    entries: Vec<([f64; 3], City<N>)>,
+}
+
+impl<N> CityIndex<N> {
+    pub fn new(cities: Vec<City<N>>) -> CityIndex<N> {
+        let mut entries: Vec<_> = cities
+            .into_iter()
+            .map(|city| (unit_vector(city.coordinates()), city))
+            .collect();
+        build(&mut entries, 0);
+        CityIndex { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // The indexed cities, in tree order rather than the order given.
+    pub fn cities(&self) -> impl Iterator<Item = &City<N>> + '_ {
+        self.entries.iter().map(|(_, city)| city)
+    }
+
+    // The `k` cities closest to `to`, nearest first.
+    pub fn nearest<P: Into<Coordinates>>(&self, to: P, k: usize) -> Vec<(&City<N>, Distance)> {
+        let target = unit_vector(to.into());
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0..self.len(), 0, target, k, &mut best);
+        }
+        self.sorted(best.into_vec())
+    }
+
+    // Every city within `radius` of `to`, nearest first.
+    pub fn within<P>(&self, to: P, radius: Distance) -> Vec<(&City<N>, Distance)>
+    where
+        P: Into<Coordinates>,
+    {
+        let target = unit_vector(to.into());
+        // Half the globe's circumference or more takes in everything.
+        let angle = (radius.meters() / EARTH_RADIUS_M).min(std::f64::consts::PI);
+        let chord = 2.0 * (angle / 2.0).sin();
+        let mut found = Vec::new();
+        self.search_within(0..self.len(), 0, target, chord * chord, &mut found);
+        self.sorted(found)
+    }
+
+    // The searches work on index ranges of `self.entries`, each of which is
+    // a subtree with its root in the middle.
+    fn search_nearest(
+        &self,
+        range: Range<usize>,
+        axis: usize,
+        target: [f64; 3],
+        k: usize,
+        best: &mut BinaryHeap<Candidate>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let mid = range.start + range.len() / 2;
+        let point = self.entries[mid].0;
+
+        best.push(Candidate {
+            chord_sqr: chord_sqr(point, target),
+            index: mid,
+        });
+        if best.len() > k {
+            best.pop();
+        }
+
+        let (left, right) = (range.start..mid, mid + 1..range.end);
+        let diff = target[axis] - point[axis];
+        let (near, far) = if diff < 0.0 { (left, right) } else { (right, left) };
+        let next = (axis + 1) % 3;
+        self.search_nearest(near, next, target, k, best);
+        // The far side can only help if the splitting plane is closer than
+        // the worst candidate so far.
+        if best.len() < k || best.peek().is_some_and(|worst| diff * diff < worst.chord_sqr) {
+            self.search_nearest(far, next, target, k, best);
+        }
+    }
+
+    fn search_within(
+        &self,
+        range: Range<usize>,
+        axis: usize,
+        target: [f64; 3],
+        limit: f64,
+        found: &mut Vec<Candidate>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let mid = range.start + range.len() / 2;
+        let point = self.entries[mid].0;
+
+        let d = chord_sqr(point, target);
+        if d <= limit {
+            found.push(Candidate { chord_sqr: d, index: mid });
+        }
+
+        let diff = target[axis] - point[axis];
+        let next = (axis + 1) % 3;
+        if diff <= 0.0 || diff * diff <= limit {
+            self.search_within(range.start..mid, next, target, limit, found);
+        }
+        if diff >= 0.0 || diff * diff <= limit {
+            self.search_within(mid + 1..range.end, next, target, limit, found);
+        }
+    }
+
+    fn sorted(&self, mut candidates: Vec<Candidate>) -> Vec<(&City<N>, Distance)> {
+        candidates.sort();
+        candidates
+            .into_iter()
+            .map(|c| (&self.entries[c.index].1, chord_to_distance(c.chord_sqr)))
+            .collect()
+    }
+}
+
+impl<N> FromIterator<City<N>> for CityIndex<N> {
+    fn from_iter<I: IntoIterator<Item = City<N>>>(cities: I) -> Self {
+        CityIndex::new(cities.into_iter().collect())
+    }
+}
+
+// Arrange `entries` so the median on `axis` is in the middle, with smaller
+// values before it and larger ones after, then do the same for each half on
+// the next axis.
+fn build<N>(entries: &mut [([f64; 3], City<N>)], axis: usize) {
+    if entries.len() <= 1 {
+        return;
+    }
+    let mid = entries.len() / 2;
+    entries.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let (left, right) = entries.split_at_mut(mid);
+    build(left, (axis + 1) % 3);
+    build(&mut right[1..], (axis + 1) % 3);
+}
+
+// A city found by a search. Ordered by distance, so that a `BinaryHeap`
+// keeps the farthest on top, ready to be evicted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    chord_sqr: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.chord_sqr
+            .total_cmp(&other.chord_sqr)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+fn unit_vector(at: Coordinates) -> [f64; 3] {
+    let (sin_lat, cos_lat) = at.lat.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = at.lon.to_radians().sin_cos();
+    [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+fn chord_sqr(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+// A chord of length `c` spans an angle of `2·asin(c / 2)`; this is the
+// haversine formula in another guise.
+fn chord_to_distance(chord_sqr: f64) -> Distance {
+    let angle = 2.0 * (chord_sqr.sqrt() / 2.0).min(1.0).asin();
+    Distance::from_meters(EARTH_RADIUS_M * angle)
+}
@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::color::Color;
+
+// An RGB raster image, row by row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Color>,
+}
+
+// Why a PPM file could not be read.
+#[derive(Debug)]
+pub enum PpmError {
+    Io(io::Error),
+    // The file does not start with `P3` or `P6`.
+    NotPpm,
+    // The width, height or maximum value is missing or not a number.
+    BadHeader,
+    // A maximum channel value other than 1 to 255. 16-bit PPMs are not
+    // supported.
+    UnsupportedMaxValue(u32),
+    // Fewer pixels than the header promised, or a sample over the maximum.
+    BadPixelData,
+}
+
+impl Display for PpmError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PpmError::Io(e) => write!(f, "cannot read image: {}", e),
+            PpmError::NotPpm => write!(f, "not a PPM image"),
+            PpmError::BadHeader => write!(f, "invalid PPM header"),
+            PpmError::UnsupportedMaxValue(max) => {
+                write!(f, "unsupported maximum channel value {}", max)
+            }
+            PpmError::BadPixelData => write!(f, "truncated or invalid pixel data"),
+        }
+    }
+}
+
+impl Error for PpmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PpmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PpmError {
+    fn from(e: io::Error) -> Self {
+        PpmError::Io(e)
+    }
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Color) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    // Apply `f` to every pixel.
+    pub fn map<F: FnMut(Color) -> Color>(&self, f: F) -> Image {
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().copied().map(f).collect(),
+        }
+    }
+
+    // Reads a binary (`P6`) or plain-text (`P3`) PPM with up to 8 bits per
+    // channel. Samples with a maximum below 255 are scaled up.
+    pub fn read_ppm<R: Read>(mut reader: R) -> Result<Image, PpmError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut header = Header { data: &data, pos: 0 };
+        let binary = match header.token() {
+            Some(b"P6") => true,
+            Some(b"P3") => false,
+            _ => return Err(PpmError::NotPpm),
+        };
+        let width = header.number().ok_or(PpmError::BadHeader)? as usize;
+        let height = header.number().ok_or(PpmError::BadHeader)? as usize;
+        let max = header.number().ok_or(PpmError::BadHeader)?;
+        if !(1..=255).contains(&max) {
+            return Err(PpmError::UnsupportedMaxValue(max));
+        }
+
+        // Three samples per pixel.
+        let len = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or(PpmError::BadHeader)?;
+        let samples: Vec<u32> = if binary {
+            // Exactly one whitespace byte separates the header from the
+            // pixels, which may themselves look like whitespace.
+            let start = header.pos + 1;
+            let bytes = data.get(start..).ok_or(PpmError::BadPixelData)?;
+            bytes.iter().take(len).map(|&b| b as u32).collect()
+        } else {
+            (0..len).map_while(|_| header.number()).collect()
+        };
+        if samples.len() < len || samples.iter().any(|&s| s > max) {
+            return Err(PpmError::BadPixelData);
+        }
+
+        let scale = |s: u32| ((s * 255 + max / 2) / max) as u8;
+        let pixels = samples
+            .chunks(3)
+            .map(|rgb| Color {
+                red: scale(rgb[0]),
+                green: scale(rgb[1]),
+                blue: scale(rgb[2]),
+            })
+            .collect();
+        Ok(Image { width, height, pixels })
+    }
+
+    pub fn load_ppm<P: AsRef<Path>>(path: P) -> Result<Image, PpmError> {
+        Image::read_ppm(BufReader::new(File::open(path)?))
+    }
+
+    // Writes a binary (`P6`) PPM.
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in &self.pixels {
+            writer.write_all(&[pixel.red, pixel.green, pixel.blue])?;
+        }
+        writer.flush()
+    }
+
+    pub fn save_ppm<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_ppm(BufWriter::new(File::create(path)?))
+    }
+}
+
+// Whitespace-separated header fields, where `#` starts a comment that runs
+// to the end of the line.
+struct Header<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Header<'a> {
+    fn token(&mut self) -> Option<&'a [u8]> {
+        loop {
+            match self.data.get(self.pos)? {
+                b'#' => {
+                    while self.data.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+        Some(&self.data[start..self.pos])
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        std::str::from_utf8(self.token()?).ok()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Image, PpmError};
+
+    #[test]
+    fn huge_dimensions_are_a_bad_header() {
+        // Both fit in `u32`, and on 64-bit targets so does `width * height`;
+        // it is the three samples per pixel that overflow.
+        for header in ["P6 4294967295 4294967295 255\n", "P6 4294967295 1431655766 255\n"] {
+            let error = Image::read_ppm(header.as_bytes()).unwrap_err();
+            assert!(matches!(error, PpmError::BadHeader), "{}", header);
+        }
+    }
+
+    #[test]
+    fn dimensions_beyond_u32_are_a_bad_header() {
+        for header in ["P3 4294967296 1 255\n", "P3 1 6148914691236517206 255\n"] {
+            let error = Image::read_ppm(header.as_bytes()).unwrap_err();
+            assert!(matches!(error, PpmError::BadHeader), "{}", header);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use crate::geo::{Coordinates, NonFiniteCoordinates};
+
+// A day in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+// Returned by `Date::new` for a day that does not exist, like 30 February.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Display for InvalidDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02} is not a date", self.year, self.month, self.day)
+    }
+}
+
+impl Error for InvalidDate {}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Date, InvalidDate> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => 0,
+        };
+        if (1..=days_in_month).contains(&day) {
+            Ok(Date { year, month, day })
+        } else {
+            Err(InvalidDate { year, month, day })
+        }
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    // The Julian day number at 0:00 UTC, after Meeus.
+    fn julian_day(self) -> f64 {
+        let (mut y, mut m) = (self.year as f64, self.month as f64);
+        if m <= 2.0 {
+            y -= 1.0;
+            m += 12.0;
+        }
+        let a = (y / 100.0).floor();
+        let b = 2.0 - a + (a / 4.0).floor();
+        (365.25 * (y + 4716.0)).floor() + (30.6001 * (m + 1.0)).floor() + self.day as f64 + b
+            - 1524.5
+    }
+}
+
+// ISO 8601, `2024-06-21`.
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// A time in UTC, in minutes from midnight at the start of the date it was
+// computed for. West of Greenwich a sunset can fall after the next UTC
+// midnight, so the value may run past 1440 (or, far east, below 0).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct UtcTime(f64);
+
+impl UtcTime {
+    pub fn minutes(self) -> f64 {
+        self.0
+    }
+
+    // Whole days before or after the date, usually 0.
+    pub fn day_offset(self) -> i64 {
+        (self.0.round() / 1440.0).floor() as i64
+    }
+}
+
+// `HH:MM`, rounded to the minute, with the day offset if there is one:
+// `03:12 (+1 day)`.
+impl Display for UtcTime {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let minutes = self.0.round() as i64;
+        let in_day = minutes.rem_euclid(1440);
+        write!(f, "{:02}:{:02}", in_day / 60, in_day % 60)?;
+        match self.day_offset() {
+            0 => Ok(()),
+            offset => write!(f, " ({:+} day)", offset),
+        }
+    }
+}
+
+// Whether the sun crosses a given altitude on a day. Near the poles it may
+// not: for sunrise and sunset that is polar day or polar night, and for civil
+// twilight `PolarDay` means it never gets darker than twilight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Daylight {
+    // The sun climbs past the altitude at `rise` and sinks below it at `set`.
+    Normal { rise: UtcTime, set: UtcTime },
+    // The sun stays above the altitude all day.
+    PolarDay,
+    // The sun stays below the altitude all day.
+    PolarNight,
+}
+
+impl Daylight {
+    // How long the sun spends above the altitude.
+    pub fn duration(self) -> Duration {
+        let minutes = match self {
+            Daylight::Normal { rise, set } => set.0 - rise.0,
+            Daylight::PolarDay => 1440.0,
+            Daylight::PolarNight => 0.0,
+        };
+        Duration::from_secs_f64(minutes * 60.0)
+    }
+}
+
+impl Display for Daylight {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Daylight::Normal { rise, set } => write!(f, "{} to {}", rise, set),
+            Daylight::PolarDay => write!(f, "polar day"),
+            Daylight::PolarNight => write!(f, "polar night"),
+        }
+    }
+}
+
+// The sun's day at one place, all times in UTC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolarDay {
+    // When the sun is highest, which happens even in polar day and night.
+    pub solar_noon: UtcTime,
+    // Sunrise and sunset.
+    pub sun: Daylight,
+    // Civil dawn and dusk, when the sun's centre is 6° below the horizon.
+    pub civil_twilight: Daylight,
+}
+
+impl SolarDay {
+    pub fn day_length(&self) -> Duration {
+        self.sun.duration()
+    }
+}
+
+// The altitude of the sun's centre at sunrise and sunset: refraction lifts it
+// by about 34′, and its upper limb shows 16′ before its centre does.
+const SUNRISE_ALTITUDE: f64 = -0.833;
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+impl Coordinates {
+    // Sunrise, sunset, solar noon and civil twilight on `date`, using the
+    // NOAA solar calculator's equations. They are good to a minute or so
+    // between latitudes 72° north and south, and less certain beyond, where
+    // the sun skims the horizon. Fails if either coordinate is NaN or
+    // infinite, as there is no sensible answer.
+    pub fn solar_day(self, date: Date) -> Result<SolarDay, NonFiniteCoordinates> {
+        if !self.is_finite() {
+            return Err(NonFiniteCoordinates(self));
+        }
+        let jd = date.julian_day();
+
+        // Solar noon depends on the equation of time at noon, so estimate it
+        // from mean noon and correct it once.
+        let mut noon = 720.0 - 4.0 * self.lon;
+        for _ in 0..2 {
+            noon = 720.0 - 4.0 * self.lon - SunPosition::at(jd, noon).equation_of_time;
+        }
+
+        Ok(SolarDay {
+            solar_noon: UtcTime(noon),
+            sun: self.crossing(jd, noon, SUNRISE_ALTITUDE),
+            civil_twilight: self.crossing(jd, noon, CIVIL_TWILIGHT_ALTITUDE),
+        })
+    }
+
+    // When the sun passes `altitude` on either side of `noon`. Each time is
+    // refined by recomputing the sun's position at the previous estimate.
+    fn crossing(self, jd: f64, noon: f64, altitude: f64) -> Daylight {
+        let mut times = [noon, noon];
+        for _ in 0..3 {
+            for (time, side) in times.iter_mut().zip([-1.0, 1.0]) {
+                let sun = SunPosition::at(jd, *time);
+                match self.hour_angle(sun.declination, altitude) {
+                    Ok(h) => {
+                        let noon = 720.0 - 4.0 * self.lon - sun.equation_of_time;
+                        *time = noon + side * 4.0 * h;
+                    }
+                    Err(daylight) => return daylight,
+                }
+            }
+        }
+        Daylight::Normal {
+            rise: UtcTime(times[0]),
+            set: UtcTime(times[1]),
+        }
+    }
+
+    // The hour angle in degrees at which the sun reaches `altitude`, or
+    // which polar case applies if it never does.
+    fn hour_angle(self, declination: f64, altitude: f64) -> Result<f64, Daylight> {
+        let (phi, delta) = (self.lat.to_radians(), declination.to_radians());
+        let cos_h = (altitude.to_radians().sin() - phi.sin() * delta.sin())
+            / (phi.cos() * delta.cos());
+        if cos_h > 1.0 {
+            Err(Daylight::PolarNight)
+        } else if cos_h < -1.0 {
+            Err(Daylight::PolarDay)
+        } else {
+            Ok(cos_h.acos().to_degrees())
+        }
+    }
+}
+
+// What the NOAA equations need to know about the sun at one moment.
+struct SunPosition {
+    // Degrees north of the celestial equator.
+    declination: f64,
+    // Apparent minus mean solar time, in minutes.
+    equation_of_time: f64,
+}
+
+impl SunPosition {
+    // The sun's position `minutes` after 0:00 UTC on Julian day `jd`.
+    fn at(jd: f64, minutes: f64) -> SunPosition {
+        let t = (jd + minutes / 1440.0 - 2451545.0) / 36525.0;
+
+        let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+        let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+        let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+
+        let m = mean_anomaly.to_radians();
+        let center = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+            + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+            + (3.0 * m).sin() * 0.000289;
+        let omega = (125.04 - 1934.136 * t).to_radians();
+        let apparent_longitude = mean_longitude + center - 0.00569 - 0.00478 * omega.sin();
+
+        let mean_obliquity =
+            23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+        let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+        let declination = (obliquity.sin() * apparent_longitude.to_radians().sin()).asin();
+
+        let y = (obliquity / 2.0).tan().powi(2);
+        let l0 = mean_longitude.to_radians();
+        let e = eccentricity;
+        let equation_of_time = 4.0
+            * (y * (2.0 * l0).sin() - 2.0 * e * m.sin() + 4.0 * e * y * m.sin() * (2.0 * l0).cos()
+                - 0.5 * y * y * (4.0 * l0).sin()
+                - 1.25 * e * e * (2.0 * m).sin())
+            .to_degrees();
+
+        SunPosition {
+            declination: declination.to_degrees(),
+            equation_of_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+    use crate::geo::{Coordinates, NonFiniteCoordinates};
+
+    #[test]
+    fn non_finite_coordinates_have_no_solar_day() {
+        let date = Date::new(2024, 6, 21).unwrap();
+        for (lat, lon) in [(f64::NAN, 0.0), (f64::INFINITY, 0.0), (53.3, f64::NEG_INFINITY)] {
+            let at = Coordinates::new(lat, lon);
+            assert!(matches!(at.solar_day(date), Err(NonFiniteCoordinates(_))));
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt::{self, Display};
+
+// How a column's cells are padded to the column width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+// The output flavours a `Table` can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    // Text framed with box-drawing characters.
+    Boxed,
+    // A GitHub-flavoured Markdown table with alignment markers.
+    Markdown,
+    // RFC 4180 comma-separated values, unpadded and quoted where needed.
+    Csv,
+}
+
+// One column of a table: a header and a way to get a displayable value out
+// of each row. The precision, if any, is applied as in `{:.3}`, so it rounds
+// numbers and truncates text.
+pub struct Column<'a, R> {
+    header: String,
+    cell: CellFn<'a, R>,
+    align: Align,
+    precision: Option<usize>,
+}
+
+impl<'a, R> Column<'a, R> {
+    pub fn new<V, F>(header: &str, accessor: F) -> Column<'a, R>
+    where
+        V: Display,
+        F: Fn(&R) -> V + 'a,
+    {
+        Column {
+            header: header.to_string(),
+            cell: Box::new(move |row, precision| match precision {
+                Some(p) => format!("{:.*}", p, accessor(row)),
+                None => accessor(row).to_string(),
+            }),
+            align: Align::Left,
+            precision: None,
+        }
+    }
+
+    pub fn align(self, align: Align) -> Column<'a, R> {
+        Column { align, ..self }
+    }
+
+    pub fn precision(self, precision: usize) -> Column<'a, R> {
+        Column {
+            precision: Some(precision),
+            ..self
+        }
+    }
+}
+
+// Formats a row's value for one column at the given precision.
+type CellFn<'a, R> = Box<dyn Fn(&R, Option<usize>) -> String + 'a>;
+
+// A set of columns that can render any slice of rows.
+pub struct Table<'a, R> {
+    columns: Vec<Column<'a, R>>,
+}
+
+impl<'a, R> Table<'a, R> {
+    pub fn new(columns: Vec<Column<'a, R>>) -> Table<'a, R> {
+        Table { columns }
+    }
+
+    // A `Display` adapter, so a table can go straight into `println!`.
+    pub fn display<'t>(&'t self, rows: &'t [R], format: TableFormat) -> TableDisplay<'t, 'a, R> {
+        TableDisplay {
+            table: self,
+            rows,
+            format,
+        }
+    }
+
+    pub fn render(&self, rows: &[R], format: TableFormat) -> String {
+        self.display(rows, format).to_string()
+    }
+
+    // Every cell as text, header row first.
+    fn cells(&self, rows: &[R]) -> Vec<Vec<String>> {
+        let header = self.columns.iter().map(|c| c.header.clone()).collect();
+        let body = rows.iter().map(|row| {
+            self.columns
+                .iter()
+                .map(|c| (c.cell)(row, c.precision))
+                .collect()
+        });
+        std::iter::once(header).chain(body).collect()
+    }
+}
+
+pub struct TableDisplay<'t, 'a, R> {
+    table: &'t Table<'a, R>,
+    rows: &'t [R],
+    format: TableFormat,
+}
+
+impl<R> Display for TableDisplay<'_, '_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self.table.cells(self.rows);
+        let aligns: Vec<Align> = self.table.columns.iter().map(|c| c.align).collect();
+
+        match self.format {
+            TableFormat::Boxed => write_boxed(f, &cells, &aligns),
+            TableFormat::Markdown => write_markdown(f, &cells, &aligns),
+            TableFormat::Csv => write_csv(f, &cells),
+        }
+    }
+}
+
+// The widest cell in each column, measured in terminal columns.
+fn column_widths(cells: &[Vec<String>]) -> Vec<usize> {
+    let mut widths = vec![0; cells.first().map_or(0, Vec::len)];
+    for row in cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+    widths
+}
+
+// `std`'s own padding counts `char`s, which goes wrong for wide and
+// combining characters, so cells are padded by hand.
+fn write_padded(f: &mut fmt::Formatter, text: &str, width: usize, align: Align) -> fmt::Result {
+    let padding = width.saturating_sub(display_width(text));
+    let (before, after) = match align {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+    write!(f, "{:before$}{}{:after$}", "", text, "", before = before, after = after)
+}
+
+fn write_boxed(f: &mut fmt::Formatter, cells: &[Vec<String>], aligns: &[Align]) -> fmt::Result {
+    let widths = column_widths(cells);
+    let rule = |f: &mut fmt::Formatter, left: &str, middle: &str, right: &str| {
+        f.write_str(left)?;
+        for (i, width) in widths.iter().enumerate() {
+            if i != 0 {
+                f.write_str(middle)?;
+            }
+            f.write_str(&"─".repeat(width + 2))?;
+        }
+        writeln!(f, "{}", right)
+    };
+
+    rule(f, "┌", "┬", "┐")?;
+    for (r, row) in cells.iter().enumerate() {
+        f.write_str("│")?;
+        for ((cell, &width), &align) in row.iter().zip(&widths).zip(aligns) {
+            f.write_str(" ")?;
+            // Headers are always centred over their column.
+            let align = if r == 0 { Align::Center } else { align };
+            write_padded(f, cell, width, align)?;
+            f.write_str(" │")?;
+        }
+        writeln!(f)?;
+        if r == 0 {
+            rule(f, "├", "┼", "┤")?;
+        }
+    }
+    rule(f, "└", "┴", "┘")
+}
+
+fn write_markdown(f: &mut fmt::Formatter, cells: &[Vec<String>], aligns: &[Align]) -> fmt::Result {
+    // A pipe inside a cell would end it early.
+    let cells: Vec<Vec<String>> = cells
+        .iter()
+        .map(|row| row.iter().map(|c| c.replace('|', "\\|")).collect())
+        .collect();
+    // The delimiter row needs at least three dashes per column.
+    let widths: Vec<usize> = column_widths(&cells).into_iter().map(|w| w.max(3)).collect();
+
+    for (r, row) in cells.iter().enumerate() {
+        f.write_str("|")?;
+        for ((cell, &width), &align) in row.iter().zip(&widths).zip(aligns) {
+            f.write_str(" ")?;
+            write_padded(f, cell, width, align)?;
+            f.write_str(" |")?;
+        }
+        writeln!(f)?;
+
+        if r == 0 {
+            f.write_str("|")?;
+            for (&width, &align) in widths.iter().zip(aligns) {
+                let dashes = "-".repeat(width);
+                match align {
+                    Align::Left => write!(f, " :{} |", &dashes[1..])?,
+                    Align::Right => write!(f, " {}: |", &dashes[1..])?,
+                    Align::Center => write!(f, " :{}: |", &dashes[2..])?,
+                }
+            }
+            writeln!(f)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_csv(f: &mut fmt::Formatter, cells: &[Vec<String>]) -> fmt::Result {
+    for row in cells {
+        for (i, cell) in row.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            if cell.contains([',', '"', '\n', '\r']) {
+                write!(f, "\"{}\"", cell.replace('"', "\"\""))?;
+            } else {
+                f.write_str(cell)?;
+            }
+        }
+        // RFC 4180 ends every record with CRLF.
+        f.write_str("\r\n")?;
+    }
+    Ok(())
+}
+
+// How many terminal columns `text` occupies: East Asian wide and fullwidth
+// characters and most emoji take two, combining marks and other zero-width
+// characters take none, and everything else takes one. The ranges cover the
+// commonly used blocks rather than the full Unicode tables.
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+fn char_width(c: char) -> usize {
+    let cp = c as u32;
+
+    const ZERO: &[(u32, u32)] = &[
+        (0x0300, 0x036F), // combining diacritical marks
+        (0x0483, 0x0489),
+        (0x0591, 0x05BD),
+        (0x0610, 0x061A),
+        (0x064B, 0x065F),
+        (0x1AB0, 0x1AFF),
+        (0x1DC0, 0x1DFF),
+        (0x200B, 0x200F), // zero-width space, joiners, direction marks
+        (0x2028, 0x202E),
+        (0x2060, 0x2064),
+        (0x20D0, 0x20FF), // combining marks for symbols
+        (0xFE00, 0xFE0F), // variation selectors
+        (0xFE20, 0xFE2F),
+        (0xFEFF, 0xFEFF),
+        (0xE0100, 0xE01EF),
+    ];
+    const WIDE: &[(u32, u32)] = &[
+        (0x1100, 0x115F), // Hangul Jamo initials
+        (0x2E80, 0x303E), // CJK radicals, punctuation
+        (0x3041, 0x33FF), // kana, CJK compatibility
+        (0x3400, 0x4DBF), // CJK extension A
+        (0x4E00, 0x9FFF), // CJK unified ideographs
+        (0xA000, 0xA4CF), // Yi
+        (0xAC00, 0xD7A3), // Hangul syllables
+        (0xF900, 0xFAFF), // CJK compatibility ideographs
+        (0xFE30, 0xFE4F),
+        (0xFF00, 0xFF60), // fullwidth forms
+        (0xFFE0, 0xFFE6),
+        (0x1F300, 0x1F64F), // pictographs and emoticons
+        (0x1F900, 0x1F9FF),
+        (0x20000, 0x3FFFD), // CJK extensions B onwards
+    ];
+
+    let within = |ranges: &[(u32, u32)]| ranges.iter().any(|&(lo, hi)| lo <= cp && cp <= hi);
+    if c.is_control() || within(ZERO) {
+        0
+    } else if within(WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{display_width, Align, Column, Table, TableFormat};
+
+    type Row = (&'static str, f64, &'static str);
+
+    // A wide CJK name and emoji, and a cell with a comma, quotes and a pipe.
+    fn sample() -> (Table<'static, Row>, Vec<Row>) {
+        let table = Table::new(vec![
+            Column::new("City", |r: &Row| r.0),
+            Column::new("Pop.", |r: &Row| r.1).align(Align::Right).precision(1),
+            Column::new("Note", |r: &Row| r.2).align(Align::Center),
+        ]);
+        let rows = vec![("東京", 37.43, "🎉"), ("Foo, \"Bar\"", 1.06, "a|b")];
+        (table, rows)
+    }
+
+    #[test]
+    fn boxed() {
+        let (table, rows) = sample();
+        let expected = "\
+┌────────────┬──────┬──────┐
+│    City    │ Pop. │ Note │
+├────────────┼──────┼──────┤
+│ 東京       │ 37.4 │  🎉  │
+│ Foo, \"Bar\" │  1.1 │ a|b  │
+└────────────┴──────┴──────┘
+";
+        assert_eq!(table.render(&rows, TableFormat::Boxed), expected);
+    }
+
+    #[test]
+    fn markdown_escapes_pipes() {
+        let (table, rows) = sample();
+        let expected = "\
+| City       | Pop. | Note |
+| :--------- | ---: | :--: |
+| 東京       | 37.4 |  🎉  |
+| Foo, \"Bar\" |  1.1 | a\\|b |
+";
+        assert_eq!(table.render(&rows, TableFormat::Markdown), expected);
+    }
+
+    #[test]
+    fn csv_quotes_commas_and_quotes() {
+        let (table, rows) = sample();
+        let expected = "City,Pop.,Note\r\n東京,37.4,🎉\r\n\"Foo, \"\"Bar\"\"\",1.1,a|b\r\n";
+        assert_eq!(table.render(&rows, TableFormat::Csv), expected);
+    }
+
+    #[test]
+    fn display_width_counts_terminal_columns() {
+        assert_eq!(display_width("東京"), 4);
+        assert_eq!(display_width("🎉"), 2);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("Foo"), 3);
+    }
+}
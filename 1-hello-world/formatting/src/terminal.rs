@@ -0,0 +1,228 @@
+use std::env;
+use std::fmt::{self, Display, Formatter};
+
+use crate::color::Color;
+
+// How many colours a terminal can show, from none at all to full 24-bit
+// colour. The variants are ordered, so `support >= ColorSupport::Ansi256`
+// works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    // Plain text, with no escape codes at all.
+    None,
+    // The 16 basic colours, whose exact shades depend on the terminal theme.
+    Ansi16,
+    // The xterm 256-colour palette.
+    Ansi256,
+    // Any RGB colour.
+    TrueColor,
+}
+
+impl ColorSupport {
+    // Works out what the current terminal supports from the environment. See
+    // `from_env`.
+    pub fn detect() -> ColorSupport {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let colorterm = env::var("COLORTERM").ok();
+        let term = env::var("TERM").ok();
+        ColorSupport::from_env(no_color, colorterm.as_deref(), term.as_deref())
+    }
+
+    // A non-empty `NO_COLOR` turns colour off whatever else is set, as
+    // https://no-color.org asks. Otherwise `COLORTERM=truecolor` (or `24bit`)
+    // means 24-bit colour, a `TERM` like `xterm-256color` means the 256-colour
+    // palette, `TERM=dumb` means none, and anything else gets the basic 16.
+    pub fn from_env(no_color: bool, colorterm: Option<&str>, term: Option<&str>) -> ColorSupport {
+        if no_color {
+            ColorSupport::None
+        } else if matches!(colorterm, Some("truecolor" | "24bit")) {
+            ColorSupport::TrueColor
+        } else if term.is_some_and(|t| t.contains("256color")) {
+            ColorSupport::Ansi256
+        } else if term == Some("dumb") {
+            ColorSupport::None
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+}
+
+// The colours xterm uses by default for the 16 basic indices. Themes are free
+// to change these, so they are only a guide.
+const ANSI16: [Color; 16] = [
+    rgb(0, 0, 0),
+    rgb(205, 0, 0),
+    rgb(0, 205, 0),
+    rgb(205, 205, 0),
+    rgb(0, 0, 238),
+    rgb(205, 0, 205),
+    rgb(0, 205, 205),
+    rgb(229, 229, 229),
+    rgb(127, 127, 127),
+    rgb(255, 0, 0),
+    rgb(0, 255, 0),
+    rgb(255, 255, 0),
+    rgb(92, 92, 255),
+    rgb(255, 0, 255),
+    rgb(0, 255, 255),
+    rgb(255, 255, 255),
+];
+
+// The channel levels of the 6×6×6 colour cube at indices 16 to 231.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+const fn rgb(red: u8, green: u8, blue: u8) -> Color {
+    Color { red, green, blue }
+}
+
+// The colour xterm shows for a palette index: the 16 basic colours, then the
+// colour cube, then a 24-step grey ramp from 8 to 238.
+pub fn xterm_color(index: u8) -> Color {
+    match index {
+        0..=15 => ANSI16[index as usize],
+        16..=231 => {
+            let i = index as usize - 16;
+            rgb(CUBE_LEVELS[i / 36], CUBE_LEVELS[i / 6 % 6], CUBE_LEVELS[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            rgb(level, level, level)
+        }
+    }
+}
+
+// The index in `candidates` whose colour looks closest to `color`.
+fn nearest(color: Color, candidates: impl Iterator<Item = u8>) -> u8 {
+    candidates
+        .map(|i| (i, color.delta_e2000(xterm_color(i))))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+impl Color {
+    // The closest colour in the 256-colour palette. Only the cube and the
+    // grey ramp are considered, since the first 16 depend on the theme.
+    pub fn to_ansi256(self) -> u8 {
+        nearest(self, 16..=255)
+    }
+
+    // The closest of the 16 basic colours, assuming xterm's defaults.
+    pub fn to_ansi16(self) -> u8 {
+        nearest(self, 0..=15)
+    }
+
+    // A `Display` adapter that prints a block of this colour, two columns
+    // wide, as the background of some spaces. The terminal's support is
+    // detected from the environment; use `Swatch::support` to override it.
+    pub fn swatch(self) -> Swatch {
+        Swatch {
+            color: self,
+            width: 2,
+            support: ColorSupport::detect(),
+        }
+    }
+
+    // A `Display` adapter that prints `text` in this colour. Any width or
+    // precision is passed on to `text`.
+    pub fn paint<D: Display>(self, text: D) -> Painted<D> {
+        Painted {
+            color: self,
+            text,
+            support: ColorSupport::detect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swatch {
+    pub color: Color,
+    pub width: usize,
+    pub support: ColorSupport,
+}
+
+impl Swatch {
+    pub fn width(self, width: usize) -> Swatch {
+        Swatch { width, ..self }
+    }
+
+    pub fn support(self, support: ColorSupport) -> Swatch {
+        Swatch { support, ..self }
+    }
+}
+
+// Without colour the swatch is still printed as spaces, so that whatever
+// follows it stays lined up.
+impl Display for Swatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let on = write_escape(f, self.color, self.support, Layer::Background)?;
+        write!(f, "{:width$}", "", width = self.width)?;
+        write_reset(f, on)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Painted<D> {
+    pub color: Color,
+    pub text: D,
+    pub support: ColorSupport,
+}
+
+impl<D> Painted<D> {
+    pub fn support(self, support: ColorSupport) -> Painted<D> {
+        Painted { support, ..self }
+    }
+}
+
+impl<D: Display> Display for Painted<D> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let on = write_escape(f, self.color, self.support, Layer::Foreground)?;
+        self.text.fmt(f)?;
+        write_reset(f, on)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Layer {
+    Foreground,
+    Background,
+}
+
+// Writes the SGR escape that selects `color` at the best depth `support`
+// allows, and returns whether anything was written.
+fn write_escape(
+    f: &mut Formatter,
+    color: Color,
+    support: ColorSupport,
+    layer: Layer,
+) -> Result<bool, fmt::Error> {
+    // Foreground codes start at 30 (38 for extended colours), and background
+    // codes are the same plus 10.
+    let offset = match layer {
+        Layer::Foreground => 0,
+        Layer::Background => 10,
+    };
+    match support {
+        ColorSupport::None => return Ok(false),
+        ColorSupport::Ansi16 => {
+            let index = color.to_ansi16();
+            // The bright colours have codes of their own, from 90.
+            let code = if index < 8 { 30 + index } else { 90 + index - 8 };
+            write!(f, "\x1b[{}m", code + offset)?;
+        }
+        ColorSupport::Ansi256 => write!(f, "\x1b[{};5;{}m", 38 + offset, color.to_ansi256())?,
+        ColorSupport::TrueColor => {
+            let Color { red, green, blue } = color;
+            write!(f, "\x1b[{};2;{};{};{}m", 38 + offset, red, green, blue)?;
+        }
+    }
+    Ok(true)
+}
+
+fn write_reset(f: &mut Formatter, on: bool) -> fmt::Result {
+    if on {
+        f.write_str("\x1b[0m")
+    } else {
+        Ok(())
+    }
+}
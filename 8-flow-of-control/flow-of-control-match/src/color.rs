@@ -0,0 +1,195 @@
+use std::error::Error;
+use std::fmt;
+
+// Activity: convert between the colour models.
+//
+// Units and ranges of the components, checked by `validate`:
+// - `RGB`: red, green and blue, each 0 to 255.
+// - `HSV` and `HSL`: hue in degrees, 0 to 359, then saturation and value or
+//   lightness in percent, 0 to 100.
+// - `CMY` and `CMYK`: cyan, magenta, yellow and key (black) in percent, 0 to
+//   100.
+// The named colours are the RGB primaries, so `Green` is `RGB(0, 255, 0)`
+// rather than CSS's darker `green`.
+//
+// Components are whole numbers, so converting rounds. Converting an `RGB`
+// colour to any other model and back lands within 3 of each original
+// channel: a percentage step is 2.55 RGB steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// The variant names are the models' usual abbreviations.
+#[allow(clippy::upper_case_acronyms)]
+// `allow` required to silence warnings because only one variant is used.
+#[allow(dead_code)]
+pub enum Color {
+    // These 3 are specified solely by their name.
+    Red,
+    Blue,
+    Green,
+    // These likewise tie `u32` tuples to different names: color models.
+    RGB(u32, u32, u32),
+    HSV(u32, u32, u32),
+    HSL(u32, u32, u32),
+    CMY(u32, u32, u32),
+    CMYK(u32, u32, u32, u32),
+}
+
+// A component outside the range its model allows, e.g. `RGB(300, 0, 0)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidColor {
+    pub model: &'static str,
+    pub component: &'static str,
+    pub value: u32,
+    pub max: u32,
+}
+
+impl fmt::Display for InvalidColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is {}, but must be between 0 and {}",
+            self.model, self.component, self.value, self.max
+        )
+    }
+}
+
+impl Error for InvalidColor {}
+
+impl Color {
+    // Check every component against the ranges listed above.
+    pub fn validate(self) -> Result<(), InvalidColor> {
+        let (model, components): (&str, &[(&str, u32, u32)]) = match self {
+            Color::Red | Color::Blue | Color::Green => return Ok(()),
+            Color::RGB(r, g, b) => ("RGB", &[("red", r, 255), ("green", g, 255), ("blue", b, 255)]),
+            Color::HSV(h, s, v) => {
+                ("HSV", &[("hue", h, 359), ("saturation", s, 100), ("value", v, 100)])
+            }
+            Color::HSL(h, s, l) => {
+                ("HSL", &[("hue", h, 359), ("saturation", s, 100), ("lightness", l, 100)])
+            }
+            Color::CMY(c, m, y) => {
+                ("CMY", &[("cyan", c, 100), ("magenta", m, 100), ("yellow", y, 100)])
+            }
+            Color::CMYK(c, m, y, k) => (
+                "CMYK",
+                &[("cyan", c, 100), ("magenta", m, 100), ("yellow", y, 100), ("key", k, 100)],
+            ),
+        };
+
+        match components.iter().find(|(_, value, max)| value > max) {
+            Some(&(component, value, max)) => Err(InvalidColor {
+                model,
+                component,
+                value,
+                max,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn to_rgb(self) -> Result<Color, InvalidColor> {
+        let [r, g, b] = self.rgb_fractions()?;
+        let byte = |x: f64| (x * 255.0).round() as u32;
+        Ok(Color::RGB(byte(r), byte(g), byte(b)))
+    }
+
+    pub fn to_hsv(self) -> Result<Color, InvalidColor> {
+        let [r, g, b] = self.rgb_fractions()?;
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Ok(Color::HSV(hue(r, g, b), percent(saturation), percent(max)))
+    }
+
+    pub fn to_hsl(self) -> Result<Color, InvalidColor> {
+        let [r, g, b] = self.rgb_fractions()?;
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Ok(Color::HSL(hue(r, g, b), percent(saturation), percent(lightness)))
+    }
+
+    pub fn to_cmy(self) -> Result<Color, InvalidColor> {
+        let [r, g, b] = self.rgb_fractions()?;
+        Ok(Color::CMY(percent(1.0 - r), percent(1.0 - g), percent(1.0 - b)))
+    }
+
+    // Black is taken out as `K`, so at least one of `C`, `M` and `Y` is 0.
+    pub fn to_cmyk(self) -> Result<Color, InvalidColor> {
+        let [r, g, b] = self.rgb_fractions()?;
+        let k = 1.0 - r.max(g).max(b);
+        if k == 1.0 {
+            return Ok(Color::CMYK(0, 0, 0, 100));
+        }
+        let ink = |x: f64| percent((1.0 - x - k) / (1.0 - k));
+        Ok(Color::CMYK(ink(r), ink(g), ink(b), percent(k)))
+    }
+
+    // Red, green and blue as fractions from 0 to 1, the hub every
+    // conversion goes through.
+    fn rgb_fractions(self) -> Result<[f64; 3], InvalidColor> {
+        self.validate()?;
+        let fraction = |x: u32, max: f64| x as f64 / max;
+
+        Ok(match self {
+            Color::Red => [1.0, 0.0, 0.0],
+            Color::Green => [0.0, 1.0, 0.0],
+            Color::Blue => [0.0, 0.0, 1.0],
+            Color::RGB(r, g, b) => [r, g, b].map(|x| fraction(x, 255.0)),
+            Color::HSV(h, s, v) => {
+                let (s, v) = (fraction(s, 100.0), fraction(v, 100.0));
+                let chroma = v * s;
+                from_hue(h, chroma, v - chroma)
+            }
+            Color::HSL(h, s, l) => {
+                let (s, l) = (fraction(s, 100.0), fraction(l, 100.0));
+                let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+                from_hue(h, chroma, l - chroma / 2.0)
+            }
+            Color::CMY(c, m, y) => [c, m, y].map(|x| 1.0 - fraction(x, 100.0)),
+            Color::CMYK(c, m, y, k) => {
+                let k = fraction(k, 100.0);
+                [c, m, y].map(|x| (1.0 - fraction(x, 100.0)) * (1.0 - k))
+            }
+        })
+    }
+}
+
+// The hue in whole degrees, `[0, 360)`, of a colour given as fractions. Greys
+// have no hue; they get 0.
+fn hue(r: f64, g: f64, b: f64) -> u32 {
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let delta = max - min;
+    if delta == 0.0 {
+        return 0;
+    }
+    let sector = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (sector * 60.0).round() as u32 % 360
+}
+
+// The RGB fractions for a hue with the given chroma, lifted by `m`.
+fn from_hue(h: u32, chroma: f64, m: f64) -> [f64; 3] {
+    let sector = h as f64 / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let [r, g, b] = match h / 60 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    [r + m, g + m, b + m]
+}
+
+fn percent(x: f64) -> u32 {
+    (x * 100.0).round() as u32
+}
@@ -1,17 +1,6 @@
-// `allow` required to silence warnings because only one variant is used.
-#[allow(dead_code)]
-enum Color {
-    // These 3 are specified solely by their name.
-    Red,
-    Blue,
-    Green,
-    // These likewise tie `u32` tuples to different names: color models.
-    RGB(u32, u32, u32),
-    HSV(u32, u32, u32),
-    HSL(u32, u32, u32),
-    CMY(u32, u32, u32),
-    CMYK(u32, u32, u32, u32),
-}
+mod color;
+
+use color::Color;
 
 struct StructEx {
     x: (u32, u32),
@@ -150,6 +139,47 @@ fn main() {
         // Don't need another arm because all variants have been examined
     }
 
+    // Any variant converts to any model, going through RGB.
+    println!("As HSV: {:?}", color.to_hsv().unwrap());
+    println!("As HSL: {:?}", color.to_hsl().unwrap());
+    println!("As CMYK: {:?}", color.to_cmyk().unwrap());
+    assert_eq!(Color::Red.to_hsl(), Ok(Color::HSL(0, 100, 50)));
+    assert_eq!(Color::Green.to_rgb(), Ok(Color::RGB(0, 255, 0)));
+    assert_eq!(Color::HSL(210, 100, 50).to_rgb(), Ok(Color::RGB(0, 128, 255)));
+    assert_eq!(Color::Blue.to_cmyk(), Ok(Color::CMYK(100, 100, 0, 0)));
+
+    // Round trips through every model stay within 3 of each RGB channel.
+    let mut worst = 0;
+    for r in (0..=255).step_by(5) {
+        for g in (0..=255).step_by(5) {
+            for b in (0..=255).step_by(5) {
+                let original = Color::RGB(r, g, b);
+                let converted = [
+                    original.to_hsv(),
+                    original.to_hsl(),
+                    original.to_cmy(),
+                    original.to_cmyk(),
+                ];
+                for color in converted {
+                    let Ok(Color::RGB(r2, g2, b2)) = color.and_then(|c| c.to_rgb()) else {
+                        unreachable!()
+                    };
+                    worst = worst.max(r.abs_diff(r2)).max(g.abs_diff(g2)).max(b.abs_diff(b2));
+                }
+            }
+        }
+    }
+    println!("Largest round-trip error: {}", worst);
+    assert!(worst <= 3);
+
+    // Components outside their model's range are rejected, not wrapped or
+    // clamped.
+    match Color::RGB(300, 0, 0).to_hsv() {
+        Ok(hsv) => println!("Converted to {:?}", hsv),
+        Err(e) => println!("Invalid color: {}", e),
+    }
+    assert!(Color::HSV(360, 50, 50).validate().is_err());
+
     /*
     For pointers, a distinction needs to be made between destructuring and
     dereferencing as they are different concepts which are used differently